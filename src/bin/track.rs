@@ -13,9 +13,7 @@ fn main() -> Result<(), Report<AppError>> {
     init::error_reporting();
     init::tracing();
 
-    let _ = cli::run()
+    cli::run()
         .change_context(AppError)
-        .attach_printable("failed to run CLI");
-
-    Ok(())
+        .attach_printable("failed to run CLI")
 }
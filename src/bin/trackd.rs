@@ -0,0 +1,19 @@
+use error_stack::{Report, ResultExt};
+use track::{error::AppError, init};
+
+#[cfg(unix)]
+fn main() -> Result<(), Report<AppError>> {
+    init::error_reporting();
+    init::tracing();
+
+    track::feature::cli::run_daemon()
+        .change_context(AppError)
+        .attach_printable("failed to run daemon")
+}
+
+#[cfg(not(unix))]
+fn main() -> Result<(), Report<AppError>> {
+    init::error_reporting();
+
+    Err(Report::new(AppError).attach_printable("trackd needs Unix domain sockets"))
+}
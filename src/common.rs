@@ -1 +1,104 @@
 //! functionality shared
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer};
+
+/// The instant `date` begins in `tz`.
+pub fn start_of_day(date: NaiveDate, tz: &Tz) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// The longest duration [`parse_duration`] accepts, the most `chrono::Duration` can hold.
+const MAX_SECONDS: u64 = i64::MAX as u64 / 1000;
+
+/// Parses a human duration such as `90s`, `25m`, `1h30m` or `7d`.
+///
+/// A bare number is taken as seconds.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("duration is empty".to_string());
+    }
+    let too_long = || format!("duration '{text}' is too long");
+    if let Ok(seconds) = text.parse::<u64>() {
+        return match seconds <= MAX_SECONDS {
+            true => Ok(Duration::from_secs(seconds)),
+            false => Err(too_long()),
+        };
+    }
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for ch in text.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let unit = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 60 * 60 * 24,
+            'w' => 60 * 60 * 24 * 7,
+            _ => return Err(format!("unknown duration unit '{ch}' in '{text}'")),
+        };
+        let value: u64 = digits
+            .parse()
+            .map_err(|_| format!("missing number before '{ch}' in '{text}'"))?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|seconds| total.checked_add(seconds))
+            .filter(|total| *total <= MAX_SECONDS)
+            .ok_or_else(too_long)?;
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        return Err(format!("missing unit after '{digits}' in '{text}'"));
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// Serde helper for settings written like `"8h"`, see [`parse_duration`].
+pub fn deserialize_duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Duration, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    parse_duration(&text).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_seconds() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn parses_combined_units() {
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("7d"), Ok(Duration::from_secs(7 * 86400)));
+    }
+
+    #[test]
+    fn rejects_unknown_units() {
+        assert!(parse_duration("5y").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("1h30").is_err());
+    }
+
+    #[test]
+    fn rejects_durations_that_overflow() {
+        assert!(parse_duration("99999999999999h").is_err());
+        assert!(parse_duration("18446744073709551615s1s").is_err());
+        assert!(parse_duration("18446744073709551615").is_err());
+        assert!(parse_duration("100000w").is_ok());
+    }
+}
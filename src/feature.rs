@@ -1,4 +1,23 @@
 
 pub mod cli;
 pub mod tracker;
-pub  mod report_fmt;
\ No newline at end of file
+pub  mod report_fmt;
+pub mod balance;
+pub mod budget;
+pub mod calendar;
+pub mod config;
+#[cfg(unix)]
+pub mod daemon;
+pub mod export;
+pub mod git;
+pub mod goals;
+pub mod hooks;
+pub mod import;
+pub mod invoice;
+pub mod metrics;
+pub mod pomodoro;
+pub mod prompt;
+pub mod serve;
+pub mod stats;
+pub mod timeline;
+pub mod tui;
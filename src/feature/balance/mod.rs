@@ -0,0 +1,115 @@
+use std::{collections::BTreeSet, path::Path};
+
+use chrono::NaiveDate;
+use error_stack::{Report, Result, ResultExt};
+
+use super::{
+    report_fmt::{format_signed, DurationFormat, HMSFormatter},
+    tracker::reporter::DayBalance,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("flextime balance error")]
+pub struct BalanceError;
+
+/// Reads a holidays file: one `YYYY-MM-DD` per line, optionally followed by a name.
+///
+/// Blank lines and lines starting with `#` are ignored.
+pub fn read_holidays<P: AsRef<Path>>(path: P) -> Result<BTreeSet<NaiveDate>, BalanceError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .change_context(BalanceError)
+        .attach_printable_lazy(|| format!("unable to read {}", path.display()))?;
+    parse_holidays(&text)
+}
+
+fn parse_holidays(text: &str) -> Result<BTreeSet<NaiveDate>, BalanceError> {
+    let mut holidays = BTreeSet::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let date = line.split_whitespace().next().unwrap_or_default();
+        let date = date.parse().map_err(|_| {
+            Report::new(BalanceError)
+                .attach_printable(format!("line {}: '{date}' is not a date", index + 1))
+        })?;
+        holidays.insert(date);
+    }
+    Ok(holidays)
+}
+
+/// Sum of the daily differences between tracked and expected time.
+pub fn total(days: &[DayBalance]) -> chrono::Duration {
+    days.iter().map(DayBalance::difference).sum()
+}
+
+/// One line per day with expected or tracked time, with the running balance.
+pub fn render_days(days: &[DayBalance]) -> String {
+    let mut text = String::new();
+    let mut balance = chrono::Duration::zero();
+    for day in days {
+        balance += day.difference();
+        if day.expected.is_zero() && day.actual.is_zero() {
+            continue;
+        }
+        text.push_str(&format!(
+            "{}  {}  {}  {}  {}\n",
+            day.date.format("%a %Y-%m-%d"),
+            HMSFormatter.format(day.actual),
+            HMSFormatter.format(day.expected),
+            format_signed(day.difference()),
+            format_signed(balance),
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 12, day).unwrap()
+    }
+
+    #[test]
+    fn parses_holidays_with_names_and_comments() {
+        let holidays = parse_holidays("# Germany\n2026-12-25 Christmas\n\n2026-12-26\n").unwrap();
+
+        assert_eq!(holidays, BTreeSet::from([day(25), day(26)]));
+        assert!(parse_holidays("25.12.2026").is_err());
+    }
+
+    #[test]
+    fn renders_running_balance_for_days_with_time() {
+        let hours = |hours: u64| Duration::from_secs(hours * 3600);
+        let days = [
+            DayBalance {
+                date: day(21),
+                expected: hours(8),
+                actual: hours(9),
+            },
+            DayBalance {
+                date: day(27),
+                expected: hours(0),
+                actual: hours(0),
+            },
+            DayBalance {
+                date: day(28),
+                expected: hours(8),
+                actual: hours(6),
+            },
+        ];
+
+        assert_eq!(total(&days), chrono::Duration::hours(-1));
+        assert_eq!(
+            render_days(&days),
+            "Mon 2026-12-21  09:00:00  08:00:00  +01:00:00  +01:00:00\n\
+             Mon 2026-12-28  06:00:00  08:00:00  -02:00:00  -01:00:00\n"
+        );
+    }
+}
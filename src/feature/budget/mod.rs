@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use super::{
+    report_fmt::{DurationFormat, HMSFormatter},
+    tracker::{reporter::BudgetUsage, Task},
+};
+
+/// Burn-down of each budget: the total so far, then the time left after every tracked week.
+pub fn render(usage: &[BudgetUsage]) -> String {
+    if usage.is_empty() {
+        return "no budgets configured; add \"budgets\" to the config file\n".to_string();
+    }
+    let mut text = String::new();
+    for project in usage {
+        text.push_str(&format!(
+            "{}  {} / {}  {:.0}%  {}\n",
+            project.project,
+            HMSFormatter.format(project.used()),
+            HMSFormatter.format(project.budget.limit),
+            project.fraction() * 100.0,
+            left(project.budget.limit, project.used())
+        ));
+        let mut used = Duration::ZERO;
+        for (monday, worked) in &project.weeks {
+            used += *worked;
+            text.push_str(&format!(
+                "  week of {monday}  {}  {}\n",
+                HMSFormatter.format(*worked),
+                left(project.budget.limit, used)
+            ));
+        }
+    }
+    text
+}
+
+/// Warning shown when a session starts on a project past one of its alert thresholds.
+pub fn warning(usage: &BudgetUsage) -> Option<String> {
+    let alert = usage.alert()?;
+    Some(format!(
+        "Warning: {} has used {:.0}% of its budget, past {alert}% ({} of {}, {})",
+        usage.project,
+        usage.fraction() * 100.0,
+        HMSFormatter.format(usage.used()),
+        HMSFormatter.format(usage.budget.limit),
+        left(usage.budget.limit, usage.used())
+    ))
+}
+
+/// A task's estimate next to the time actually spent on it.
+pub fn task_line(task: &Task, actual: Duration) -> String {
+    let percent = match task.estimate.is_zero() {
+        true => String::new(),
+        false => format!(
+            " ({:.0}%)",
+            actual.as_secs_f64() / task.estimate.as_secs_f64() * 100.0
+        ),
+    };
+    format!(
+        "{}  estimate {}  actual {}  {}{percent}",
+        task.name,
+        HMSFormatter.format(task.estimate),
+        HMSFormatter.format(actual),
+        left(task.estimate, actual)
+    )
+}
+
+fn left(limit: Duration, used: Duration) -> String {
+    match used > limit {
+        true => format!("{} over", HMSFormatter.format(used - limit)),
+        false => format!("{} left", HMSFormatter.format(limit - used)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::feature::tracker::reporter::Budget;
+
+    fn hours(hours: u64) -> Duration {
+        Duration::from_secs(hours * 3600)
+    }
+
+    fn usage(weeks: &[(u32, u64)]) -> BudgetUsage {
+        BudgetUsage {
+            project: "web".to_string(),
+            budget: Budget {
+                limit: hours(40),
+                alerts: vec![50, 80, 100],
+            },
+            weeks: weeks
+                .iter()
+                .map(|(day, worked)| {
+                    let monday = NaiveDate::from_ymd_opt(2026, 9, *day).unwrap();
+                    (monday, hours(*worked))
+                })
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn burn_down_lists_the_time_left_after_each_week() {
+        let text = render(&[usage(&[(7, 20), (14, 14), (21, 10)])]);
+
+        assert_eq!(
+            text,
+            "web  44:00:00 / 40:00:00  110%  04:00:00 over\n  \
+             week of 2026-09-07  20:00:00  20:00:00 left\n  \
+             week of 2026-09-14  14:00:00  06:00:00 left\n  \
+             week of 2026-09-21  10:00:00  04:00:00 over\n"
+        );
+    }
+
+    #[test]
+    fn warns_about_the_highest_threshold_passed() {
+        assert_eq!(warning(&usage(&[(7, 10)])), None);
+        assert_eq!(
+            warning(&usage(&[(7, 34)])).unwrap(),
+            "Warning: web has used 85% of its budget, past 80% (34:00:00 of 40:00:00, 06:00:00 left)"
+        );
+    }
+
+    #[test]
+    fn compares_estimate_and_actual_time() {
+        let task = Task {
+            name: "login".to_string(),
+            project: None,
+            estimate: hours(4),
+            closed: None,
+        };
+
+        assert_eq!(
+            task_line(&task, hours(5)),
+            "login  estimate 04:00:00  actual 05:00:00  01:00:00 over (125%)"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{Datelike, Days, NaiveDate};
+use owo_colors::OwoColorize;
+
+use super::report_fmt::{DurationFormat, HMSFormatter};
+
+/// Upper bound in hours for each shade; longer days get the darkest one.
+const LEVELS: [f64; 4] = [0.0, 2.0, 4.0, 6.0];
+const WEEKDAYS: &str = "Mon Tue Wed Thu Fri Sat Sun";
+
+/// First day shown when the heatmap ends with the week of `last_day`.
+pub fn first_day(last_day: NaiveDate, weeks: u32) -> NaiveDate {
+    monday_of(last_day) - Days::new(7 * u64::from(weeks.saturating_sub(1)))
+}
+
+fn monday_of(day: NaiveDate) -> NaiveDate {
+    day - Days::new(day.weekday().num_days_from_monday().into())
+}
+
+/// Renders a heatmap with one row per week, Monday first, ending with the week of `last_day`.
+///
+/// Shades are plain characters when `color` is false.
+pub fn render(
+    totals: &BTreeMap<NaiveDate, Duration>,
+    last_day: NaiveDate,
+    weeks: u32,
+    color: bool,
+) -> String {
+    let last_monday = monday_of(last_day);
+    let first_monday = first_day(last_day, weeks);
+
+    // each day is a shade under the middle letter of its name
+    let mut text = format!("{:10}  {WEEKDAYS}  {:>8}\n", "week of", "total");
+    let mut monday = first_monday;
+    while monday <= last_monday {
+        let mut week_total = Duration::ZERO;
+        let mut cells = Vec::with_capacity(7);
+        for offset in 0..7 {
+            let day = monday + Days::new(offset);
+            if day > last_day {
+                cells.push("   ".to_string());
+                continue;
+            }
+            let total = totals.get(&day).copied().unwrap_or_default();
+            week_total += total;
+            cells.push(format!(" {} ", cell(total, color)));
+        }
+        text.push_str(&format!(
+            "{}  {}  {:>8}\n",
+            monday.format("%Y-%m-%d"),
+            cells.join(" "),
+            HMSFormatter.format(week_total)
+        ));
+        monday = monday + Days::new(7);
+    }
+    text.push_str(&legend(color));
+    text
+}
+
+fn level(total: Duration) -> usize {
+    let hours = total.as_secs_f64() / 3600.0;
+    if total.is_zero() {
+        return 0;
+    }
+    LEVELS.iter().filter(|bound| hours > **bound).count()
+}
+
+fn cell(total: Duration, color: bool) -> String {
+    let (shade, colored) = match level(total) {
+        0 => ("·", "·".dimmed().to_string()),
+        1 => ("░", "░".green().to_string()),
+        2 => ("▒", "▒".green().to_string()),
+        3 => ("▓", "▓".bright_green().to_string()),
+        _ => ("█", "█".bright_green().to_string()),
+    };
+    match color {
+        true => colored,
+        false => shade.to_string(),
+    }
+}
+
+fn legend(color: bool) -> String {
+    let hours = |level: usize| Duration::from_secs_f64(LEVELS[level] * 3600.0 + 1.0);
+    format!(
+        "\n{} none  {} <2h  {} 2-4h  {} 4-6h  {} 6h+\n",
+        cell(Duration::ZERO, color),
+        cell(hours(0), color),
+        cell(hours(1), color),
+        cell(hours(2), color),
+        cell(hours(3), color),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn shades_grow_with_hours() {
+        assert_eq!(level(Duration::ZERO), 0);
+        assert_eq!(level(Duration::from_secs(60)), 1);
+        assert_eq!(level(Duration::from_secs(3 * 3600)), 2);
+        assert_eq!(level(Duration::from_secs(5 * 3600)), 3);
+        assert_eq!(level(Duration::from_secs(9 * 3600)), 4);
+    }
+
+    #[test]
+    fn one_row_per_week_with_weekly_totals() {
+        let totals = BTreeMap::from([
+            (day(6), Duration::from_secs(3600)),
+            (day(13), Duration::from_secs(2 * 3600)),
+            (day(14), Duration::from_secs(30 * 60)),
+        ]);
+
+        // Sunday 2026-10-18 closes the third week
+        let text = render(&totals, day(18), 3, false);
+
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines[0].contains(WEEKDAYS));
+        assert!(lines[1].starts_with("2026-09-28") && lines[1].ends_with("00:00:00"));
+        assert!(lines[2].starts_with("2026-10-05") && lines[2].ends_with("01:00:00"));
+        assert!(lines[3].starts_with("2026-10-12") && lines[3].ends_with("02:30:00"));
+        assert!(lines.last().unwrap().contains("6h+"));
+    }
+
+    #[test]
+    fn shades_line_up_with_their_weekday_and_totals_with_the_header() {
+        let totals = BTreeMap::from([(day(12), Duration::from_secs(3600))]);
+
+        // Wednesday 2026-10-14 leaves the rest of the week empty
+        let text = render(&totals, day(18) - Days::new(4), 2, false);
+
+        let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let header = &lines[0];
+        let shades = ['░', '·', '·', ' ', ' ', ' ', ' '];
+        for (index, name) in WEEKDAYS.split(' ').enumerate() {
+            let column = 12 + 4 * index + 1;
+            assert_eq!(header[column], name.chars().nth(1).unwrap());
+            assert_eq!(lines[1][column], '·');
+            assert_eq!(lines[2][column], shades[index]);
+        }
+        assert_eq!(lines[1].len(), header.len());
+        assert_eq!(lines[2].len(), header.len());
+        assert!(!text.contains('\u{1b}'));
+    }
+}
@@ -1,31 +1,368 @@
-use std::{path::PathBuf, time::Duration};
+use std::{
+    fs::File,
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
 
-use clap::{Parser, Subcommand};
+use chrono::{DateTime, Days, Months, NaiveDate, Utc};
+use chrono_tz::Tz;
+use clap::{Args, Parser, Subcommand};
 use error_stack::{Result, ResultExt};
 
 use crate::{
+    common::{parse_duration, start_of_day},
     error::Suggestion,
     feature::{
-        report_fmt::{DurationFormat, HMSFormatter},
+        balance, budget, calendar,
+        config::Config,
+        export::ExportFormat,
+        git::{self, Checkout},
+        goals,
+        hooks::{HookedTracker, Hooks},
+        import::{ImportPlan, ImportSource},
+        invoice::{self, InvoiceFormat, InvoiceRequest},
+        metrics,
+        pomodoro::{self, Plan},
+        prompt,
+        report_fmt::{self, compare, format_signed, log_line, DurationFormat, HMSFormatter},
+        serve, stats, timeline,
         tracker::{
-            reporter::{ReportTimespan, Reporter},
-            Tracker,
+            reporter::{GroupBy, Period, ReportTimespan, Reporter},
+            ActiveSession, EndTime, PauseStatus, ResumeStatus, SessionDetails, Task, TimeRecord,
+            Tracker, DEFAULT_TIMER,
         },
+        tui,
     },
 };
 
+#[cfg(unix)]
+use crate::feature::daemon::{self, DaemonTracker};
+
 use super::tracker::{flatfile::FlatFileTracker, StartupStatus};
 
 #[derive(Debug, thiserror::Error)]
 #[error("a cli error occured")]
 pub struct CliError;
-#[derive(Debug, Clone, Copy, Subcommand)]
+#[derive(Debug, Clone, Subcommand)]
 pub enum Command {
     // start tracking time
-    Start,
+    Start {
+        #[arg(short = 'p', long)]
+        project: Option<String>,
+        #[arg(short = 't', long = "tag")]
+        tags: Vec<String>,
+        /// describe what the session is about
+        #[arg(short = 'm', long = "message")]
+        note: Option<String>,
+        /// time that is not charged to the client
+        #[arg(long)]
+        non_billable: bool,
+        /// work on an estimated task; its project is used unless -p is given
+        #[arg(long)]
+        task: Option<String>,
+        /// do not take project and tags from the git checkout
+        #[arg(long)]
+        no_git: bool,
+    },
     Stop,
-    Report,
+    /// List running timers
+    Status,
+    /// Compact status of the running timer for shell prompts and status bars
+    Prompt {
+        /// placeholders: {project} {tags} {note} {task} {timer} {state} {elapsed} {elapsed_hms}
+        #[arg(short = 'f', long, default_value = prompt::DEFAULT_FORMAT)]
+        format: String,
+        /// text shown when the timer is not running
+        #[arg(long, default_value = "")]
+        idle: String,
+        /// print a Waybar JSON block with a `running`, `paused` or `idle` class
+        #[arg(long)]
+        waybar: bool,
+    },
+    /// Take a break without ending the session
+    Pause,
+    /// Continue the session after a break
+    Resume,
+    /// Add to the note of the running session
+    Note {
+        text: String,
+    },
+    Report {
+        #[command(flatten)]
+        window: WindowArgs,
+        /// only count records whose note contains this text
+        #[arg(short = 's', long)]
+        search: Option<String>,
+        /// per project totals next to another period; the window defaults to `--this week`
+        #[arg(long, value_enum)]
+        compare: Option<Comparison>,
+        /// billable time and amounts per client and project, using the configured rates
+        #[arg(long, conflicts_with_all = ["compare", "search"])]
+        billing: bool,
+        /// burn-down of the configured project budgets, over all records
+        #[arg(
+            long,
+            conflicts_with_all = ["compare", "search", "billing", "last", "this", "from", "to"]
+        )]
+        budget: bool,
+    },
+    /// List records with their notes
+    Log {
+        #[command(flatten)]
+        window: WindowArgs,
+        /// only list records whose note contains this text
+        #[arg(short = 's', long)]
+        search: Option<String>,
+    },
+    /// Export records in the report window, or all records if no window is given
+    Export {
+        #[arg(short = 'f', long, value_enum)]
+        format: ExportFormat,
+        #[command(flatten)]
+        window: WindowArgs,
+        /// how grouped formats such as org arrange records
+        #[arg(long, value_enum, default_value_t)]
+        group_by: GroupBy,
+        /// write to this file instead of stdout
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
+    },
+    /// Bill a client's uninvoiced time of one month and mark it as invoiced
+    Invoice {
+        /// client name from the billing config
+        #[arg(long)]
+        client: String,
+        /// month to bill (YYYY-MM)
+        #[arg(long, value_parser = parse_month)]
+        month: NaiveDate,
+        #[arg(short = 'f', long, value_enum, default_value_t)]
+        format: InvoiceFormat,
+        /// one line item per project or per day
+        #[arg(long, value_enum, default_value_t)]
+        group_by: GroupBy,
+        /// write to this file instead of stdout
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
+        /// show the invoice without numbering it or marking records
+        #[arg(long)]
+        preview: bool,
+    },
+    /// Work in timed intervals with breaks, tracking each work interval
+    Pomodoro {
+        /// length of a work interval
+        #[arg(long, value_parser = parse_duration, default_value = "25m")]
+        work: Duration,
+        /// length of the break after each work interval but the last
+        #[arg(long = "break", value_parser = parse_duration, default_value = "5m")]
+        rest: Duration,
+        /// number of work intervals
+        #[arg(long, default_value_t = 4)]
+        cycles: u32,
+        #[arg(short = 'p', long)]
+        project: Option<String>,
+        #[arg(short = 't', long = "tag")]
+        tags: Vec<String>,
+    },
+    /// Heatmap of hours tracked per day
+    Calendar {
+        /// number of weeks to show, including the current one
+        #[arg(short = 'w', long, default_value_t = 12,
+              value_parser = clap::value_parser!(u32).range(1..))]
+        weeks: u32,
+    },
+    /// Bars of one day's records on an hour axis
+    Timeline {
+        /// day to show, today by default
+        #[arg(long)]
+        date: Option<NaiveDate>,
+    },
+    /// Session lengths, typical hours, streaks and the busiest weekday
+    Stats {
+        #[command(flatten)]
+        window: WindowArgs,
+    },
+    /// Progress towards the goals in the config file
+    Goals,
+    /// Mark a day as vacation or otherwise free of expected work
+    Off {
+        date: NaiveDate,
+        /// make it a regular workday again
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Flextime balance: tracked minus expected time since the schedule began
+    Balance {
+        /// last day to include, yesterday by default
+        #[arg(long)]
+        until: Option<NaiveDate>,
+        /// list every day with expected or tracked time
+        #[arg(long)]
+        daily: bool,
+    },
+    /// Estimated tasks that sessions can be started on
+    Task {
+        #[command(subcommand)]
+        action: TaskAction,
+    },
+    /// Restart the running session when another git branch is checked out
+    GitHook {
+        #[command(subcommand)]
+        action: GitHookAction,
+    },
+    /// Local JSON API for editor plugins and other tools
+    Serve {
+        /// address to listen on; there is no authentication, so only requests to localhost
+        /// are answered
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        bind: String,
+    },
+    /// Prometheus gauges of the running timers and today's and this week's totals
+    Metrics {
+        /// replace this file atomically, e.g. for the node exporter's textfile collector
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
+    },
+    /// Interactive dashboard
+    Tui,
+    /// Merge records exported from another time tracker
+    Import {
+        #[arg(long, value_enum)]
+        from: ImportSource,
+        file: PathBuf,
+        /// write the new records; without this only the summary is shown
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum TaskAction {
+    /// Add a task with an estimate
+    Add {
+        name: String,
+        /// expected effort, e.g. `3h` or `1h30m`
+        #[arg(short = 'e', long, value_parser = parse_duration)]
+        estimate: Duration,
+        #[arg(short = 'p', long)]
+        project: Option<String>,
+    },
+    /// List tasks with their estimate and the time spent so far
+    List {
+        /// include closed tasks
+        #[arg(long)]
+        all: bool,
+    },
+    /// Mark a task as done and compare its estimate with the time spent
+    Close { name: String },
 }
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum GitHookAction {
+    /// Add a post-checkout hook to the repository in the current directory
+    Install {
+        /// replace a post-checkout hook that was not installed by track
+        #[arg(long)]
+        force: bool,
+    },
+    /// Called by the installed hook with the arguments git passes to post-checkout
+    #[command(hide = true)]
+    PostCheckout {
+        previous_head: String,
+        new_head: String,
+        /// 1 for a branch checkout, 0 for a file checkout
+        branch_checkout: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Comparison {
+    /// the preceding period of the same kind
+    Previous,
+}
+
+impl Command {
+    /// Whether the command acts on the timer chosen with `--timer`.
+    fn uses_timer(&self) -> bool {
+        matches!(
+            self,
+            Command::Start { .. }
+                | Command::Stop
+                | Command::Prompt { .. }
+                | Command::Pause
+                | Command::Resume
+                | Command::Note { .. }
+                | Command::Pomodoro { .. }
+                | Command::GitHook { .. }
+                | Command::Serve { .. }
+                | Command::Metrics { .. }
+                | Command::Tui
+        )
+    }
+}
+
+/// Which records a report covers
+#[derive(Debug, Clone, Args)]
+pub struct WindowArgs {
+    /// trailing window ending now, e.g. `24h` or `7d`
+    #[arg(long, value_parser = parse_duration, conflicts_with_all = ["from", "to"])]
+    pub last: Option<Duration>,
+    /// the current calendar day, week or month
+    #[arg(long, value_enum, conflicts_with_all = ["last", "from", "to"])]
+    pub this: Option<Period>,
+    /// first day to include (YYYY-MM-DD, in the configured timezone)
+    #[arg(long)]
+    pub from: Option<NaiveDate>,
+    /// last day to include (YYYY-MM-DD, in the configured timezone)
+    #[arg(long)]
+    pub to: Option<NaiveDate>,
+}
+
+impl WindowArgs {
+    fn is_empty(&self) -> bool {
+        self.last.is_none() && self.this.is_none() && self.from.is_none() && self.to.is_none()
+    }
+
+    /// The period before this window: the previous calendar period for `--this`, otherwise
+    /// the same length just before `timespan`.
+    fn preceding(&self, timespan: ReportTimespan, tz: &Tz) -> Option<ReportTimespan> {
+        let Some(period) = self.this else {
+            return timespan.preceding();
+        };
+        let ReportTimespan::Range { from, .. } = timespan else {
+            return timespan.preceding();
+        };
+        let day_before = from.with_timezone(tz).date_naive().pred_opt()?;
+        let (from, to) = period.bounds(day_before, tz);
+        Some(ReportTimespan::Range { from, to })
+    }
+
+    fn timespan(&self, default: ReportTimespan, tz: &Tz) -> ReportTimespan {
+        if let Some(last) = self.last {
+            return ReportTimespan::Last(last);
+        }
+        if let Some(period) = self.this {
+            let today = Utc::now().with_timezone(tz).date_naive();
+            let (from, to) = period.bounds(today, tz);
+            return ReportTimespan::Range { from, to };
+        }
+        if self.is_empty() {
+            return default;
+        }
+        let from = self
+            .from
+            .map(|date| start_of_day(date, tz))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let to = self
+            .to
+            .and_then(|to| to.checked_add_days(Days::new(1)))
+            .map(|date| start_of_day(date, tz))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        ReportTimespan::Range { from, to }
+    }
+}
+
 #[derive(Debug, Clone, Parser)]
 #[command(version, about, arg_required_else_help(true))]
 struct Cli {
@@ -33,48 +370,789 @@ struct Cli {
     pub db_dir: Option<PathBuf>,
     #[arg(short = 'l', long)]
     pub lockfile: Option<PathBuf>,
+    /// settings file, `track/config.json` in the config directory by default
+    #[arg(short = 'c', long)]
+    pub config: Option<PathBuf>,
+    /// named timer to operate on; several can run at once
+    #[arg(long, global = true, default_value = DEFAULT_TIMER)]
+    pub timer: String,
+    /// socket of a running trackd, which is used instead of the files if it serves them
+    #[arg(long)]
+    pub socket: Option<PathBuf>,
     #[command(subcommand)]
     pub command: Command,
 }
 
+/// Options of the `trackd` binary.
+#[derive(Debug, Clone, Parser)]
+#[command(version, about = "Daemon that keeps the track database in memory")]
+struct DaemonCli {
+    #[arg(short = 'd', long)]
+    pub db_dir: Option<PathBuf>,
+    #[arg(short = 'l', long)]
+    pub lockfile: Option<PathBuf>,
+    /// where to listen, `track/trackd.sock` in the runtime directory by default
+    #[arg(long)]
+    pub socket: Option<PathBuf>,
+}
+
+/// Runs `trackd` until it is stopped.
+#[cfg(unix)]
+pub fn run_daemon() -> Result<(), CliError> {
+    let args = DaemonCli::parse();
+    let db_dir = resolve_db_dir(args.db_dir.as_ref())?;
+    let lockfile = resolve_lockfile(args.lockfile.as_ref())?;
+    let socket = args
+        .socket
+        .or_else(daemon::default_socket)
+        .ok_or(CliError)
+        .attach_printable("failed to discover runtime directory")
+        .attach(Suggestion("use --socket to specify a socket path"))?;
+    let tracker = FlatFileTracker::new(db_dir, lockfile)
+        .in_memory()
+        .change_context(CliError)
+        .attach_printable("failed to load the database")?;
+    daemon::serve(tracker, &socket).change_context(CliError)
+}
+
 pub fn run() -> Result<(), CliError> {
     let args = Cli::parse();
+    if args.timer != DEFAULT_TIMER && !args.command.uses_timer() {
+        return Err(CliError)
+            .attach_printable(format!(
+                "--timer {} has no effect on this command",
+                args.timer
+            ))
+            .attach(Suggestion("drop --timer, the command covers every timer"));
+    }
     let db_dir = flatfile_db_dir(&args)?;
     let lockfile = lockfile_path(&args)?;
-    let mut tracker = FlatFileTracker::new(db_dir, lockfile);
+    let config_path = config_path(&args);
+    let config = load_config(config_path.as_deref())?;
+    let tz = config.timezone();
+    // hooks live next to the config file, e.g. in `~/.config/track/hooks/`
+    let hooks = match config_path.as_deref().and_then(Path::parent) {
+        Some(dir) => Hooks::new(dir.join("hooks")),
+        None => Hooks::none(),
+    };
+    #[cfg(unix)]
+    if let Some(daemon) = args
+        .socket
+        .clone()
+        .or_else(daemon::default_socket)
+        .and_then(|socket| DaemonTracker::connect(&socket, &db_dir, &lockfile, &args.timer))
+    {
+        let tracker = HookedTracker::new(daemon, hooks, &args.timer);
+        return execute(args, config, tracker, tz);
+    }
+    let tracker = FlatFileTracker::new(db_dir, lockfile).with_timer(&args.timer);
+    let tracker = HookedTracker::new(tracker, hooks, &args.timer);
+    execute(args, config, tracker, tz)
+}
+
+/// Carries out the command, with a tracker on the files or on a running `trackd`.
+fn execute<T: Tracker>(
+    args: Cli,
+    config: Config,
+    mut tracker: HookedTracker<T>,
+    tz: Tz,
+) -> Result<(), CliError> {
     match args.command {
-        Command::Start => {
-            let state = tracker.start().unwrap();
-            if state == StartupStatus::Running {
-                println!("Tracking already started");
-            } else {
-                println!("Starting tracking time...");
+        Command::Start {
+            project,
+            tags,
+            note,
+            non_billable,
+            task,
+            no_git,
+        } => {
+            let mut project = project;
+            if let Some(name) = &task {
+                let task = open_task(&tracker, name)?;
+                project = project.or(task.project);
+            }
+            let mut details = SessionDetails {
+                project,
+                tags,
+                note,
+                non_billable,
+                task,
+            };
+            if config.git.enabled && !no_git {
+                if let Some(checkout) = current_checkout() {
+                    config.git.apply(&checkout, &mut details);
+                }
+            }
+            let project = details.project.clone();
+            let state = tracker
+                .start_with(details)
+                .change_context(CliError)
+                .attach_printable("failed to start tracking")?;
+            match state {
+                StartupStatus::Running(timer) if timer == DEFAULT_TIMER => {
+                    println!("Tracking already started")
+                }
+                StartupStatus::Running(timer) => println!("Timer '{timer}' already running"),
+                StartupStatus::Started => {
+                    println!("Starting tracking time...");
+                    let usage = tracker
+                        .budget_usage(&config.budgets, &tz)
+                        .change_context(CliError)
+                        .attach_printable("failed to compute budget usage")?;
+                    if let Some(warning) = usage
+                        .iter()
+                        .find(|usage| project.as_ref() == Some(&usage.project))
+                        .and_then(budget::warning)
+                    {
+                        println!("{warning}");
+                    }
+                }
             }
         }
         Command::Stop => {
             println!("Stopping tracking time...");
-            tracker.stop().unwrap();
+            tracker
+                .stop()
+                .change_context(CliError)
+                .attach_printable("failed to stop tracking")?;
+        }
+        Command::Status => {
+            let sessions = tracker
+                .active_sessions()
+                .change_context(CliError)
+                .attach_printable("failed to read running timers")?;
+            if sessions.is_empty() {
+                println!("No session is running");
+            }
+            let now = Utc::now();
+            for session in sessions {
+                println!("{}", status_line(&session, now, &tz));
+            }
+        }
+        Command::Prompt {
+            format,
+            idle,
+            waybar,
+        } => {
+            // reads only the lockfile, so it stays fast enough for every prompt
+            let sessions = tracker
+                .active_sessions()
+                .change_context(CliError)
+                .attach_printable("failed to read running timers")?;
+            let session = sessions.iter().find(|session| session.timer == args.timer);
+            let now = Utc::now();
+            if waybar {
+                let block = prompt::waybar(&format, session, &idle, now, &tz);
+                let json = serde_json::to_string(&block)
+                    .change_context(CliError)
+                    .attach_printable("failed to serialize the status block")?;
+                println!("{json}");
+                return Ok(());
+            }
+            match session {
+                Some(session) => println!("{}", prompt::render(&format, session, now)),
+                None if idle.is_empty() => {}
+                None => println!("{idle}"),
+            }
+        }
+        Command::Pause => {
+            if !is_running(&tracker)? {
+                println!("No session is running");
+                return Ok(());
+            }
+            let status = tracker
+                .pause()
+                .change_context(CliError)
+                .attach_printable("failed to pause the running session")?;
+            match status {
+                PauseStatus::Paused => println!("Paused tracking"),
+                PauseStatus::AlreadyPaused => println!("Tracking already paused"),
+            }
+        }
+        Command::Resume => {
+            if !is_running(&tracker)? {
+                println!("No session is running");
+                return Ok(());
+            }
+            let status = tracker
+                .resume()
+                .change_context(CliError)
+                .attach_printable("failed to resume the running session")?;
+            match status {
+                ResumeStatus::Resumed => println!("Resumed tracking"),
+                ResumeStatus::NotPaused => println!("Tracking is not paused"),
+            }
+        }
+        Command::Note { text } => {
+            if !is_running(&tracker)? {
+                return Err(CliError)
+                    .attach_printable("no session is running, the note was not saved")
+                    .attach(Suggestion("start one with `track start -m <note>`"));
+            }
+            tracker
+                .add_note(&text)
+                .change_context(CliError)
+                .attach_printable("failed to add note to the running session")?;
+            println!("Note added");
         }
-        Command::Report => {
-            println!("Generating report...");
-            let twenty_four_hours = {
-                const TWENTY_FOUR_HOURS: u64 = 60 * 60 * 24;
-                Duration::from_secs(TWENTY_FOUR_HOURS)
+        Command::Report {
+            mut window,
+            search,
+            compare: Some(Comparison::Previous),
+            ..
+        } => {
+            if window.is_empty() {
+                window.this = Some(Period::Week);
+            }
+            let timespan = window.timespan(ReportTimespan::All, &tz);
+            let previous = window
+                .preceding(timespan, &tz)
+                .ok_or(CliError)
+                .attach_printable("the window has no preceding period")
+                .attach(Suggestion("use --this, --last, or both --from and --to"))?;
+            let query = |timespan| -> Result<Vec<TimeRecord>, CliError> {
+                let mut records = tracker
+                    .records_in(timespan)
+                    .change_context(CliError)
+                    .attach_printable("failed to query records")?;
+                if let Some(search) = &search {
+                    records.retain(|rec| rec.note_contains(search));
+                }
+                Ok(records)
             };
-            let duration = tracker
-                .total_duration(ReportTimespan::Last(twenty_four_hours))
+            print!("{}", compare::render(&query(timespan)?, &query(previous)?));
+        }
+        Command::Report { budget: true, .. } => {
+            let usage = tracker
+                .budget_usage(&config.budgets, &tz)
                 .change_context(CliError)
-                .attach_printable("failed to calculate total track duration")?;
+                .attach_printable("failed to compute budget usage")?;
+            print!("{}", budget::render(&usage));
+        }
+        Command::Report {
+            window,
+            billing: true,
+            ..
+        } => {
+            let timespan = window.timespan(ReportTimespan::Last(TWENTY_FOUR_HOURS), &tz);
+            let lines = tracker
+                .billing(timespan, &config.billing)
+                .change_context(CliError)
+                .attach_printable("failed to compute billable amounts")?;
+            print!("{}", report_fmt::billing::render(&lines));
+        }
+        Command::Report {
+            window,
+            search,
+            compare: None,
+            ..
+        } => {
+            let records = window_records(&tracker, &window, search.as_deref(), &tz)?;
+            let duration: Duration = records.iter().map(TimeRecord::duration).sum();
+            let gross: Duration = records.iter().map(TimeRecord::gross_duration).sum();
             let formatter = HMSFormatter::default();
-            print!("{}", formatter.format(duration));
+            println!("{}", formatter.format(duration));
+            if gross != duration {
+                println!(
+                    "gross {}, breaks {}",
+                    formatter.format(gross),
+                    formatter.format(gross - duration)
+                );
+            }
+            let pomodoros = pomodoro::completed(&records);
+            if pomodoros > 0 {
+                println!("{pomodoros} pomodoros completed");
+            }
+            for record in records.iter().filter(|rec| rec.note.is_some()) {
+                let note = record.note.as_deref().unwrap_or_default();
+                println!("  {}  {note}", formatter.format(record.duration()));
+            }
+        }
+        Command::Log { window, search } => {
+            for record in window_records(&tracker, &window, search.as_deref(), &tz)? {
+                println!("{}", log_line(&record, &tz));
+            }
+        }
+        Command::Export {
+            format,
+            window,
+            group_by,
+            output,
+        } => {
+            let records = tracker
+                .records_in(window.timespan(ReportTimespan::All, &tz))
+                .change_context(CliError)
+                .attach_printable("failed to query records for export")?;
+            let mut out: Box<dyn Write> = match output {
+                Some(path) => Box::new(
+                    File::create(&path)
+                        .change_context(CliError)
+                        .attach_printable_lazy(|| {
+                            format!("failed to create export file {}", path.display())
+                        })?,
+                ),
+                None => Box::new(io::stdout().lock()),
+            };
+            format
+                .exporter(group_by, tz)
+                .export(&records, &mut out)
+                .change_context(CliError)
+                .attach_printable("failed to export records")?;
+        }
+        Command::Invoice {
+            client,
+            month,
+            format,
+            group_by,
+            output,
+            preview,
+        } => {
+            if !config.billing.clients.contains_key(&client) {
+                return Err(CliError)
+                    .attach_printable(format!("unknown client {client}"))
+                    .attach(Suggestion(
+                        "add the client to \"billing\" in the config file",
+                    ));
+            }
+            let (from, to) = Period::Month.bounds(month, &tz);
+            let records = tracker
+                .records_in(ReportTimespan::Range { from, to })
+                .change_context(CliError)
+                .attach_printable("failed to query records")?;
+            let request = InvoiceRequest {
+                client: &client,
+                first_day: month,
+                last_day: (month + Months::new(1)).pred_opt().unwrap_or(month),
+                date: Utc::now().with_timezone(&tz).date_naive(),
+                group_by,
+            };
+            let mut invoice =
+                invoice::build(&records, &request, &config.billing, &config.invoice, &tz)
+                    .change_context(CliError)?;
+            // create the file first so a bad path does not leave records marked as invoiced
+            let mut out: Box<dyn Write> = match output {
+                Some(path) => Box::new(
+                    File::create(&path)
+                        .change_context(CliError)
+                        .attach_printable_lazy(|| {
+                            format!("failed to create invoice file {}", path.display())
+                        })?,
+                ),
+                None => Box::new(io::stdout().lock()),
+            };
+            if !preview {
+                let number = tracker
+                    .issue_invoice(&invoice.records)
+                    .change_context(CliError)
+                    .attach_printable("failed to mark records as invoiced")?;
+                invoice.number = Some(invoice::invoice_number(&config.invoice, number));
+            }
+            out.write_all(invoice.render(format).as_bytes())
+                .change_context(CliError)
+                .attach_printable("failed to write invoice")?;
+        }
+        Command::Pomodoro {
+            work,
+            rest,
+            cycles,
+            project,
+            tags,
+        } => {
+            if is_running(&tracker)? {
+                return Err(CliError)
+                    .attach_printable("a session is already running")
+                    .attach(Suggestion(
+                        "stop it first, or use --timer to run alongside it",
+                    ));
+            }
+            let interrupted = Arc::new(AtomicBool::new(false));
+            for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+                signal_hook::flag::register(signal, Arc::clone(&interrupted))
+                    .change_context(CliError)
+                    .attach_printable("failed to handle interruptions")?;
+            }
+            let details = SessionDetails {
+                project,
+                tags,
+                ..Default::default()
+            };
+            let plan = Plan { work, rest, cycles };
+            pomodoro::run(
+                &mut tracker,
+                &args.timer,
+                &plan,
+                details,
+                &config.pomodoro,
+                &interrupted,
+                &tz,
+            )
+            .change_context(CliError)?;
+        }
+        Command::Calendar { weeks } => {
+            let today = Utc::now().with_timezone(&tz).date_naive();
+            let timespan = ReportTimespan::Range {
+                from: start_of_day(calendar::first_day(today, weeks), &tz),
+                to: DateTime::<Utc>::MAX_UTC,
+            };
+            let totals = tracker
+                .daily_totals(timespan, &tz)
+                .change_context(CliError)
+                .attach_printable("failed to aggregate daily totals")?;
+            print!("{}", calendar::render(&totals, today, weeks, use_color()));
+        }
+        Command::Timeline { date } => {
+            let date = date.unwrap_or_else(|| Utc::now().with_timezone(&tz).date_naive());
+            let mut records = tracker
+                .records_in(ReportTimespan::All)
+                .change_context(CliError)
+                .attach_printable("failed to query records")?;
+            let sessions = tracker
+                .active_sessions()
+                .change_context(CliError)
+                .attach_printable("failed to read running timers")?;
+            let now = EndTime::now();
+            records.extend(sessions.iter().map(|session| session.finish(now)));
+            print!("{}", timeline::render(&records, date, &tz, use_color()));
+        }
+        Command::Stats { window } => {
+            let today = Utc::now().with_timezone(&tz).date_naive();
+            let stats = tracker
+                .statistics(window.timespan(ReportTimespan::All, &tz), today, &tz)
+                .change_context(CliError)
+                .attach_printable("failed to compute statistics")?;
+            print!("{}", stats::render(&stats, &tz));
+        }
+        Command::Goals => {
+            if config.goals.is_empty() {
+                println!("no goals configured; add \"goals\" to the config file");
+                return Ok(());
+            }
+            let now = Utc::now();
+            let progress = tracker
+                .goal_progress(&config.goals, now, &tz)
+                .change_context(CliError)
+                .attach_printable("failed to compute goal progress")?;
+            print!("{}", goals::render(&progress, now, &tz));
+        }
+        Command::Off { date, remove } => {
+            tracker
+                .set_day_off(date, !remove)
+                .change_context(CliError)
+                .attach_printable("failed to update days off")?;
+            match remove {
+                true => println!("{date} is a workday again"),
+                false => println!("{date} marked as day off"),
+            }
+        }
+        Command::Balance { until, daily } => {
+            let Some(schedule) = &config.schedule else {
+                println!("no schedule configured; add \"schedule\" to the config file");
+                return Ok(());
+            };
+            let today = Utc::now().with_timezone(&tz).date_naive();
+            let until = until.unwrap_or(today - Days::new(1));
+            let holidays = match &schedule.holidays {
+                Some(path) => balance::read_holidays(path)
+                    .change_context(CliError)
+                    .attach_printable("failed to read holidays")?,
+                None => Default::default(),
+            };
+            let days = tracker
+                .daily_balance(schedule, &holidays, schedule.since, until, &tz)
+                .change_context(CliError)
+                .attach_printable("failed to compute the balance")?;
+            if daily {
+                print!("{}", balance::render_days(&days));
+            }
+            println!(
+                "balance {} ({} through {})",
+                format_signed(balance::total(&days)),
+                schedule.since,
+                until
+            );
+        }
+        Command::Task {
+            action:
+                TaskAction::Add {
+                    name,
+                    estimate,
+                    project,
+                },
+        } => {
+            let tasks = tracker
+                .tasks()
+                .change_context(CliError)
+                .attach_printable("failed to read tasks")?;
+            if tasks.iter().any(|task| task.name == name) {
+                return Err(CliError)
+                    .attach_printable(format!("task {name} already exists"))
+                    .attach(Suggestion("pick another name"));
+            }
+            tracker
+                .save_task(Task {
+                    name: name.clone(),
+                    project,
+                    estimate,
+                    closed: None,
+                })
+                .change_context(CliError)
+                .attach_printable("failed to save the task")?;
+            println!(
+                "Added task {name}, estimated {}",
+                HMSFormatter.format(estimate)
+            );
+        }
+        Command::Task {
+            action: TaskAction::List { all },
+        } => {
+            let tasks = tracker
+                .tasks()
+                .change_context(CliError)
+                .attach_printable("failed to read tasks")?;
+            for task in tasks.iter().filter(|task| all || task.is_open()) {
+                let actual = tracker
+                    .task_time(&task.name)
+                    .change_context(CliError)
+                    .attach_printable("failed to sum the time of the task")?;
+                let closed = match task.is_open() {
+                    true => "",
+                    false => "  (closed)",
+                };
+                println!("{}{closed}", budget::task_line(task, actual));
+            }
+        }
+        Command::Task {
+            action: TaskAction::Close { name },
+        } => {
+            let mut task = open_task(&tracker, &name)?;
+            let sessions = tracker
+                .active_sessions()
+                .change_context(CliError)
+                .attach_printable("failed to read running timers")?;
+            if sessions
+                .iter()
+                .any(|session| session.details.task.as_ref() == Some(&name))
+            {
+                return Err(CliError)
+                    .attach_printable(format!("a session on task {name} is still running"))
+                    .attach(Suggestion("stop it with `track stop` first"));
+            }
+            let actual = tracker
+                .task_time(&name)
+                .change_context(CliError)
+                .attach_printable("failed to sum the time of the task")?;
+            task.closed = Some(Utc::now());
+            tracker
+                .save_task(task.clone())
+                .change_context(CliError)
+                .attach_printable("failed to save the task")?;
+            println!("Closed {}", budget::task_line(&task, actual));
+        }
+        Command::GitHook {
+            action: GitHookAction::Install { force },
+        } => {
+            let dir = std::env::current_dir()
+                .change_context(CliError)
+                .attach_printable("failed to read the current directory")?;
+            let track = std::env::current_exe()
+                .change_context(CliError)
+                .attach_printable("failed to locate the track binary")?;
+            let path = git::install_hook(&dir, &track, force)
+                .change_context(CliError)
+                .attach(Suggestion("pass --force to replace an existing hook"))?;
+            println!("Installed {}", path.display());
+        }
+        Command::GitHook {
+            action: GitHookAction::PostCheckout {
+                branch_checkout, ..
+            },
+        } => {
+            if branch_checkout != "1" {
+                return Ok(());
+            }
+            let sessions = tracker
+                .active_sessions()
+                .change_context(CliError)
+                .attach_printable("failed to read running timers")?;
+            let Some(session) = sessions
+                .into_iter()
+                .find(|session| session.timer == args.timer)
+            else {
+                return Ok(());
+            };
+            let dir = std::env::current_dir()
+                .change_context(CliError)
+                .attach_printable("failed to read the current directory")?;
+            let Some(checkout) = Checkout::detect(&dir) else {
+                return Ok(());
+            };
+            let previous_tags = Checkout::detect_previous(&dir)
+                .map(|previous| config.git.tags(&previous))
+                .unwrap_or_default();
+            if config.git.tags(&checkout) == previous_tags {
+                return Ok(());
+            }
+            // keep the note, task and tags given by hand, replace only the branch tags
+            let mut details = session.details;
+            details.tags.retain(|tag| !previous_tags.contains(tag));
+            config.git.apply(&checkout, &mut details);
+            tracker
+                .switch(details)
+                .change_context(CliError)
+                .attach_printable("failed to switch tracking to the new branch")?;
+            println!(
+                "Switched tracking to {}",
+                checkout.branch.as_deref().unwrap_or(&checkout.repo)
+            );
+        }
+        Command::Serve { bind } => {
+            serve::run(tracker, &bind, &tz).change_context(CliError)?;
+        }
+        Command::Metrics { output } => {
+            let text =
+                metrics::render(&tracker, &args.timer, Utc::now(), &tz).change_context(CliError)?;
+            match output {
+                Some(path) => metrics::write_textfile(&path, &text).change_context(CliError)?,
+                None => print!("{text}"),
+            }
+        }
+        Command::Tui => {
+            tui::run(tracker, tz)
+                .change_context(CliError)
+                .attach_printable("failed to run the dashboard")?;
+        }
+        Command::Import { from, file, apply } => {
+            let input = File::open(&file)
+                .change_context(CliError)
+                .attach_printable_lazy(|| format!("failed to open {}", file.display()))?;
+            let incoming = from
+                .parse(input, &tz)
+                .change_context(CliError)
+                .attach_printable_lazy(|| format!("failed to parse {}", file.display()))?;
+            let existing = tracker
+                .records()
+                .change_context(CliError)
+                .attach_printable("failed to query existing records")?;
+            let total = incoming.len();
+            let plan = ImportPlan::new(existing, incoming);
+            println!(
+                "{total} records read: {} new, {} already tracked",
+                plan.new.len(),
+                plan.duplicates
+            );
+            if apply {
+                let imported = plan.new.len();
+                tracker
+                    .add_records(plan.new)
+                    .change_context(CliError)
+                    .attach_printable("failed to store imported records")?;
+                println!("Imported {imported} records");
+            } else {
+                println!("Dry run, nothing was imported. Re-run with --apply to merge.");
+            }
         }
     }
 
     Ok(())
 }
 
+/// Colors only for terminals, and only if `NO_COLOR` is unset or empty.
+fn use_color() -> bool {
+    io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+fn is_running<T: Tracker>(tracker: &T) -> Result<bool, CliError> {
+    tracker
+        .is_running()
+        .change_context(CliError)
+        .attach_printable("failed to read running timers")
+}
+
+fn status_line(session: &ActiveSession, now: DateTime<Utc>, tz: &Tz) -> String {
+    let started = session.start.datetime().with_timezone(tz);
+    let mut line = format!(
+        "{}  since {}  {}",
+        session.timer,
+        started.format("%Y-%m-%d %H:%M"),
+        HMSFormatter.format(session.elapsed(now))
+    );
+    if session.is_paused() {
+        line.push_str("  (paused)");
+    }
+    if let Some(project) = &session.details.project {
+        line.push_str(&format!("  {project}"));
+    }
+    line
+}
+
+/// Default window of reports.
+const TWENTY_FOUR_HOURS: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// Records in the window, last 24 hours by default, optionally narrowed to a note search.
+fn window_records(
+    tracker: &impl Reporter,
+    window: &WindowArgs,
+    search: Option<&str>,
+    tz: &Tz,
+) -> Result<Vec<TimeRecord>, CliError> {
+    let mut records = tracker
+        .records_in(window.timespan(ReportTimespan::Last(TWENTY_FOUR_HOURS), tz))
+        .change_context(CliError)
+        .attach_printable("failed to query records")?;
+    if let Some(search) = search {
+        records.retain(|rec| rec.note_contains(search));
+    }
+    Ok(records)
+}
+
+/// The git checkout of the current directory, if any.
+fn current_checkout() -> Option<Checkout> {
+    Checkout::detect(&std::env::current_dir().ok()?)
+}
+
+/// The task named `name`, which must exist and not be closed.
+fn open_task(tracker: &impl Tracker, name: &str) -> Result<Task, CliError> {
+    let tasks = tracker
+        .tasks()
+        .change_context(CliError)
+        .attach_printable("failed to read tasks")?;
+    match tasks.into_iter().find(|task| task.name == name) {
+        Some(task) if task.is_open() => Ok(task),
+        Some(_) => Err(CliError).attach_printable(format!("task {name} is closed")),
+        None => Err(CliError)
+            .attach_printable(format!("unknown task {name}"))
+            .attach(Suggestion("add it with `track task add`")),
+    }
+}
+
+/// Parses `YYYY-MM` into the first day of the month.
+fn parse_month(text: &str) -> std::result::Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{text}-01"), "%Y-%m-%d")
+        .map_err(|_| format!("expected a month like 2026-09, got '{text}'"))
+}
+
+/// The `-c` path, or the default config path if the platform has one.
+fn config_path(args: &Cli) -> Option<PathBuf> {
+    args.config.clone().or_else(Config::default_path)
+}
+
+fn load_config(path: Option<&Path>) -> Result<Config, CliError> {
+    let Some(path) = path else {
+        return Ok(Config::default());
+    };
+    Config::load(path)
+        .change_context(CliError)
+        .attach(Suggestion(
+            "fix or remove the config file, or pass -c with another path",
+        ))
+}
+
 fn lockfile_path(args: &Cli) -> Result<PathBuf, CliError> {
-    match &args.lockfile {
+    resolve_lockfile(args.lockfile.as_ref())
+}
+
+fn resolve_lockfile(lockfile: Option<&PathBuf>) -> Result<PathBuf, CliError> {
+    match lockfile {
         Some(lockfile) => Ok(lockfile.clone()),
         None => {
             let mut lockfile = dirs::cache_dir()
@@ -94,7 +1172,11 @@ fn lockfile_path(args: &Cli) -> Result<PathBuf, CliError> {
 }
 
 fn flatfile_db_dir(args: &Cli) -> Result<PathBuf, CliError> {
-    match &args.db_dir {
+    resolve_db_dir(args.db_dir.as_ref())
+}
+
+fn resolve_db_dir(db_dir: Option<&PathBuf>) -> Result<PathBuf, CliError> {
+    match db_dir {
         Some(db_dir) => Ok(db_dir.clone()),
         None => {
             let mut db_dirs = dirs::data_dir()
@@ -0,0 +1,180 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use chrono_tz::Tz;
+use error_stack::{Result, ResultExt};
+use serde::Deserialize;
+
+use super::git::GitSettings;
+use super::invoice::InvoiceSettings;
+use super::pomodoro::PomodoroSettings;
+use super::tracker::{
+    billing::RateTable,
+    reporter::{Budget, Goal, Schedule},
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("configuration error")]
+pub struct ConfigError;
+
+/// User settings read from `config.json` in the `track` config directory.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// IANA name such as `Europe/Berlin`; the system timezone when unset
+    pub timezone: Option<Tz>,
+    /// targets shown by `track goals`
+    pub goals: Vec<Goal>,
+    /// expected hours for `track balance`
+    pub schedule: Option<Schedule>,
+    /// clients and hourly rates for `report --billing`
+    pub billing: RateTable,
+    /// numbering, tax and rounding of `track invoice`
+    pub invoice: InvoiceSettings,
+    /// time budgets per project, shown by `report --budget`
+    pub budgets: BTreeMap<String, Budget>,
+    /// notification of `track pomodoro`
+    pub pomodoro: PomodoroSettings,
+    /// project and tags taken from the git checkout on `track start`
+    pub git: GitSettings,
+}
+
+impl Config {
+    /// `config.json` in the platform config directory, e.g. `~/.config/track/`.
+    pub fn default_path() -> Option<PathBuf> {
+        let mut path = dirs::config_dir()?;
+        path.push("track");
+        path.push("config.json");
+        Some(path)
+    }
+
+    /// Reads the config at `path`; a missing file means default settings.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file = File::open(path)
+            .change_context(ConfigError)
+            .attach_printable_lazy(|| format!("unable to open {}", path.display()))?;
+        let mut config: Self = serde_json::from_reader(file)
+            .change_context(ConfigError)
+            .attach_printable_lazy(|| format!("unable to parse {}", path.display()))?;
+        if let Some(holidays) = config
+            .schedule
+            .as_mut()
+            .and_then(|schedule| schedule.holidays.as_mut())
+        {
+            if let Some(dir) = path.parent().filter(|_| holidays.is_relative()) {
+                *holidays = dir.join(&*holidays);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Timezone used to decide days and show times of day.
+    pub fn timezone(&self) -> Tz {
+        self.timezone.unwrap_or_else(system_timezone)
+    }
+}
+
+fn system_timezone() -> Tz {
+    iana_time_zone::get_timezone()
+        .ok()
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::{fixture::PathChild, TempDir};
+
+    use super::*;
+
+    #[test]
+    fn missing_file_gives_defaults() {
+        let temp = TempDir::new().unwrap();
+
+        let config = Config::load(temp.child("config.json").path()).unwrap();
+
+        assert_eq!(config.timezone, None);
+    }
+
+    #[test]
+    fn reads_configured_timezone() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.child("config.json").path().to_path_buf();
+        std::fs::write(&path, r#"{"timezone":"Europe/Berlin"}"#).unwrap();
+
+        let config = Config::load(&path).unwrap();
+
+        assert_eq!(config.timezone(), chrono_tz::Europe::Berlin);
+    }
+
+    #[test]
+    fn reads_goals() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.child("config.json").path().to_path_buf();
+        std::fs::write(
+            &path,
+            r#"{"goals":[
+                {"period":"day","target":"8h","days":["Mon","Tue","Wed","Thu","Fri"]},
+                {"period":"week","target":"10h","project":"acme"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+
+        assert_eq!(config.goals.len(), 2);
+        assert_eq!(
+            config.goals[0].target,
+            std::time::Duration::from_secs(8 * 3600)
+        );
+        assert_eq!(config.goals[0].days.as_ref().unwrap().len(), 5);
+        assert_eq!(config.goals[1].project.as_deref(), Some("acme"));
+    }
+
+    #[test]
+    fn rejects_monthly_goals() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.child("config.json").path().to_path_buf();
+        std::fs::write(&path, r#"{"goals":[{"period":"month","target":"160h"}]}"#).unwrap();
+
+        assert!(Config::load(&path).is_err());
+    }
+
+    #[test]
+    fn resolves_holidays_next_to_the_config_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.child("config.json").path().to_path_buf();
+        std::fs::write(
+            &path,
+            r#"{"schedule":{"since":"2026-01-01","hours":{"Mon":"8h","Fri":"6h"},"holidays":"holidays.txt"}}"#,
+        )
+        .unwrap();
+
+        let schedule = Config::load(&path).unwrap().schedule.unwrap();
+
+        assert_eq!(
+            schedule.holidays,
+            Some(temp.child("holidays.txt").path().to_path_buf())
+        );
+        assert_eq!(
+            schedule.expected_on(chrono::NaiveDate::from_ymd_opt(2026, 10, 23).unwrap()),
+            std::time::Duration::from_secs(6 * 3600)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_timezones() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.child("config.json").path().to_path_buf();
+        std::fs::write(&path, r#"{"timezone":"Mars/Olympus"}"#).unwrap();
+
+        assert!(Config::load(&path).is_err());
+    }
+}
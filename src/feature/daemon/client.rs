@@ -0,0 +1,155 @@
+use std::{
+    collections::BTreeSet,
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use chrono::NaiveDate;
+use error_stack::{Report, Result, ResultExt};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use super::{same_path, Request, Response};
+use crate::feature::tracker::{
+    reporter::Reporter, ActiveSession, PauseStatus, RecordId, ResumeStatus, SessionDetails,
+    StartupStatus, Task, TimeRecord, Tracker, TrackerError,
+};
+
+/// A tracker whose calls are carried out by a running `trackd`.
+pub struct DaemonTracker {
+    socket: PathBuf,
+    timer: String,
+    next_id: AtomicU64,
+}
+
+impl Reporter for DaemonTracker {}
+
+impl DaemonTracker {
+    /// Connects to the daemon on `socket` if it serves the given database and lockfile.
+    ///
+    /// `None` when no daemon is running there or it works on other files, in which case the
+    /// caller should use the files directly.
+    pub fn connect(socket: &Path, db: &Path, lockfile: &Path, timer: &str) -> Option<Self> {
+        let tracker = Self {
+            socket: socket.to_path_buf(),
+            timer: timer.to_string(),
+            next_id: AtomicU64::new(1),
+        };
+        let hello: Value = tracker.call("hello", json!({})).ok()?;
+        let serves = |key: &str, path: &Path| {
+            hello[key]
+                .as_str()
+                .is_some_and(|served| same_path(Path::new(served), path))
+        };
+        (serves("db", db) && serves("lockfile", lockfile)).then_some(tracker)
+    }
+
+    fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        mut params: Value,
+    ) -> Result<T, TrackerError> {
+        params["timer"] = json!(self.timer);
+        let request = Request {
+            jsonrpc: "2.0".to_string(),
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            method: method.to_string(),
+            params,
+        };
+        let mut stream = UnixStream::connect(&self.socket)
+            .change_context(TrackerError)
+            .attach_printable_lazy(|| {
+                format!("unable to reach trackd at {}", self.socket.display())
+            })?;
+        let mut line = serde_json::to_string(&request).change_context(TrackerError)?;
+        line.push('\n');
+        stream
+            .write_all(line.as_bytes())
+            .change_context(TrackerError)
+            .attach_printable("unable to send request to trackd")?;
+        let mut line = String::new();
+        BufReader::new(stream)
+            .read_line(&mut line)
+            .change_context(TrackerError)
+            .attach_printable("unable to read response from trackd")?;
+        let response: Response = serde_json::from_str(&line)
+            .change_context(TrackerError)
+            .attach_printable("invalid response from trackd")?;
+        if let Some(error) = response.error {
+            return Err(Report::new(TrackerError)
+                .attach_printable(format!("trackd: {method} failed: {}", error.message)));
+        }
+        serde_json::from_value(response.result.unwrap_or(Value::Null))
+            .change_context(TrackerError)
+            .attach_printable_lazy(|| format!("unexpected result of {method} from trackd"))
+    }
+}
+
+impl Tracker for DaemonTracker {
+    fn start_with(&mut self, details: SessionDetails) -> Result<StartupStatus, TrackerError> {
+        self.call("start_with", json!({ "details": details }))
+    }
+
+    fn is_running(&self) -> Result<bool, TrackerError> {
+        self.call("is_running", json!({}))
+    }
+
+    fn active_sessions(&self) -> Result<Vec<ActiveSession>, TrackerError> {
+        self.call("active_sessions", json!({}))
+    }
+
+    fn stop(&mut self) -> Result<(), TrackerError> {
+        self.call("stop", json!({}))
+    }
+
+    fn pause(&mut self) -> Result<PauseStatus, TrackerError> {
+        self.call("pause", json!({}))
+    }
+
+    fn resume(&mut self) -> Result<ResumeStatus, TrackerError> {
+        self.call("resume", json!({}))
+    }
+
+    fn add_note(&mut self, note: &str) -> Result<(), TrackerError> {
+        self.call("add_note", json!({ "note": note }))
+    }
+
+    fn records(&self) -> Result<impl Iterator<Item = TimeRecord>, TrackerError> {
+        let records: Vec<TimeRecord> = self.call("records", json!({}))?;
+        Ok(records.into_iter())
+    }
+
+    fn add_records(&mut self, records: Vec<TimeRecord>) -> Result<(), TrackerError> {
+        self.call("add_records", json!({ "records": records }))
+    }
+
+    fn update_record(&mut self, record: TimeRecord) -> Result<(), TrackerError> {
+        self.call("update_record", json!({ "record": record }))
+    }
+
+    fn remove_record(&mut self, id: RecordId) -> Result<(), TrackerError> {
+        self.call("remove_record", json!({ "id": id }))
+    }
+
+    fn days_off(&self) -> Result<BTreeSet<NaiveDate>, TrackerError> {
+        self.call("days_off", json!({}))
+    }
+
+    fn set_day_off(&mut self, date: NaiveDate, off: bool) -> Result<(), TrackerError> {
+        self.call("set_day_off", json!({ "date": date, "off": off }))
+    }
+
+    fn issue_invoice(&mut self, ids: &[RecordId]) -> Result<u32, TrackerError> {
+        self.call("issue_invoice", json!({ "ids": ids }))
+    }
+
+    fn tasks(&self) -> Result<Vec<Task>, TrackerError> {
+        self.call("tasks", json!({}))
+    }
+
+    fn save_task(&mut self, task: Task) -> Result<(), TrackerError> {
+        self.call("save_task", json!({ "task": task }))
+    }
+}
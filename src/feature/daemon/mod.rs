@@ -0,0 +1,78 @@
+//! The `trackd` daemon: one process owns the database and serves every other `track`
+//! invocation over a Unix socket, one JSON-RPC 2.0 request per line.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod client;
+mod server;
+
+pub use client::DaemonTracker;
+pub use server::{dispatch, serve};
+
+#[derive(Debug, thiserror::Error)]
+#[error("daemon error")]
+pub struct DaemonError;
+
+/// Error codes defined by JSON-RPC 2.0.
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The tracker failed to carry out the call.
+const TRACKER_ERROR: i64 = -32000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    pub id: u64,
+    /// a [`Tracker`](super::tracker::Tracker) method name such as `start_with`
+    pub method: String,
+    /// named arguments, plus the `timer` the call applies to
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub jsonrpc: String,
+    pub id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// `track/trackd.sock` in the runtime directory, or in the cache directory without one.
+pub fn default_socket() -> Option<PathBuf> {
+    let mut path = dirs::runtime_dir().or_else(dirs::cache_dir)?;
+    path.push("track");
+    path.push("trackd.sock");
+    Some(path)
+}
+
+/// Whether two paths name the same file, even if it does not exist yet.
+fn same_path(a: &Path, b: &Path) -> bool {
+    let resolve = |path: &Path| {
+        path.canonicalize()
+            .or_else(|_| std::path::absolute(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+    resolve(a) == resolve(b)
+}
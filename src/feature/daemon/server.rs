@@ -0,0 +1,227 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::Path,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+use error_stack::{Report, Result, ResultExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use super::{
+    DaemonError, Request, Response, RpcError, INVALID_PARAMS, METHOD_NOT_FOUND, PARSE_ERROR,
+    TRACKER_ERROR,
+};
+use crate::feature::tracker::{flatfile::FlatFileTracker, Tracker, TrackerError, DEFAULT_TIMER};
+
+/// A client that stops sending halfway is dropped after this long.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A request line and where to send the response to it.
+type Call = (String, Sender<String>);
+
+/// Listens on `socket` and answers requests until the process is stopped.
+///
+/// Each connection is read on its own thread, so a slow client holds up only itself, but
+/// every call on the database is made here, one after another.
+/// A socket file left behind by a daemon that is gone is replaced.
+pub fn serve(tracker: FlatFileTracker, socket: &Path) -> Result<(), DaemonError> {
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            return Err(Report::new(DaemonError)
+                .attach_printable(format!("a daemon already listens on {}", socket.display())));
+        }
+        fs::remove_file(socket)
+            .change_context(DaemonError)
+            .attach_printable_lazy(|| format!("unable to remove stale {}", socket.display()))?;
+    }
+    if let Some(dir) = socket.parent() {
+        fs::create_dir_all(dir)
+            .change_context(DaemonError)
+            .attach_printable_lazy(|| format!("unable to create {}", dir.display()))?;
+    }
+    let listener = UnixListener::bind(socket)
+        .change_context(DaemonError)
+        .attach_printable_lazy(|| format!("unable to listen on {}", socket.display()))?;
+    // other users must not be able to drive the tracker
+    fs::set_permissions(socket, fs::Permissions::from_mode(0o600))
+        .change_context(DaemonError)
+        .attach_printable_lazy(|| format!("unable to restrict {}", socket.display()))?;
+    println!("Listening on {}", socket.display());
+
+    let (calls, received) = mpsc::channel();
+    thread::spawn(move || accept(listener, calls));
+    answer(&tracker, received);
+    Ok(())
+}
+
+/// Reads every connection on a thread of its own and passes its requests on to `calls`.
+fn accept(listener: UnixListener, calls: Sender<Call>) {
+    for stream in listener.incoming() {
+        let calls = calls.clone();
+        let result = stream.change_context(DaemonError).map(|stream| {
+            thread::spawn(move || {
+                if let Err(report) = handle_connection(stream, &calls) {
+                    tracing::warn!("{report:?}");
+                }
+            })
+        });
+        if let Err(report) = result {
+            tracing::warn!("{report:?}");
+        }
+    }
+}
+
+/// Answers the calls in the order they arrive, until no connection can send any more.
+fn answer(tracker: &FlatFileTracker, calls: Receiver<Call>) {
+    for (line, reply) in calls {
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                let timer = request.params["timer"].as_str().unwrap_or(DEFAULT_TIMER);
+                let mut on_timer = tracker.clone().with_timer(timer);
+                let outcome = dispatch(
+                    &mut on_timer,
+                    &request.method,
+                    &request.params,
+                    (tracker.db_path(), tracker.lockfile_path()),
+                );
+                response(Some(request.id), outcome)
+            }
+            Err(error) => response(None, Err(RpcError::new(PARSE_ERROR, error.to_string()))),
+        };
+        match serde_json::to_string(&response) {
+            // the client may be gone already
+            Ok(text) => _ = reply.send(text),
+            Err(error) => tracing::warn!(%error, "unable to encode response"),
+        }
+    }
+}
+
+fn handle_connection(stream: UnixStream, calls: &Sender<Call>) -> Result<(), DaemonError> {
+    stream
+        .set_read_timeout(Some(READ_TIMEOUT))
+        .change_context(DaemonError)?;
+    let mut writer = stream.try_clone().change_context(DaemonError)?;
+    for line in BufReader::new(stream).lines() {
+        let line = line
+            .change_context(DaemonError)
+            .attach_printable("unable to read request")?;
+        let (reply, response) = mpsc::channel();
+        calls
+            .send((line, reply))
+            .change_context(DaemonError)
+            .attach_printable("the daemon stopped answering")?;
+        let mut text = response
+            .recv()
+            .change_context(DaemonError)
+            .attach_printable("no response to the request")?;
+        text.push('\n');
+        writer
+            .write_all(text.as_bytes())
+            .change_context(DaemonError)
+            .attach_printable("unable to send response")?;
+    }
+    Ok(())
+}
+
+fn response(id: Option<u64>, outcome: std::result::Result<Value, RpcError>) -> Response {
+    let (result, error) = match outcome {
+        Ok(value) => (Some(value), None),
+        Err(error) => (None, Some(error)),
+    };
+    Response {
+        jsonrpc: "2.0".to_string(),
+        id,
+        result,
+        error,
+    }
+}
+
+/// Calls the [`Tracker`] method named `method` with the named arguments in `params`.
+///
+/// `hello` answers with the database and lockfile served, `paths`, so that clients can
+/// check they would work on the same files.
+pub fn dispatch<T: Tracker>(
+    tracker: &mut T,
+    method: &str,
+    params: &Value,
+    paths: (&Path, &Path),
+) -> std::result::Result<Value, RpcError> {
+    match method {
+        "hello" => Ok(json!({ "db": paths.0, "lockfile": paths.1 })),
+        "start_with" => reply(tracker.start_with(param(params, "details")?)),
+        "is_running" => reply(tracker.is_running()),
+        "active_sessions" => reply(tracker.active_sessions()),
+        "stop" => reply(tracker.stop()),
+        "pause" => reply(tracker.pause()),
+        "resume" => reply(tracker.resume()),
+        "add_note" => reply(tracker.add_note(&param::<String>(params, "note")?)),
+        "records" => reply(tracker.records().map(Iterator::collect::<Vec<_>>)),
+        "add_records" => reply(tracker.add_records(param(params, "records")?)),
+        "update_record" => reply(tracker.update_record(param(params, "record")?)),
+        "remove_record" => reply(tracker.remove_record(param(params, "id")?)),
+        "days_off" => reply(tracker.days_off()),
+        "set_day_off" => reply(tracker.set_day_off(param(params, "date")?, param(params, "off")?)),
+        "issue_invoice" => reply(tracker.issue_invoice(&param::<Vec<_>>(params, "ids")?)),
+        "tasks" => reply(tracker.tasks()),
+        "save_task" => reply(tracker.save_task(param(params, "task")?)),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method {method}"),
+        )),
+    }
+}
+
+fn param<T: DeserializeOwned>(params: &Value, name: &str) -> std::result::Result<T, RpcError> {
+    serde_json::from_value(params.get(name).cloned().unwrap_or(Value::Null))
+        .map_err(|error| RpcError::new(INVALID_PARAMS, format!("{name}: {error}")))
+}
+
+fn reply<T: Serialize>(result: Result<T, TrackerError>) -> std::result::Result<Value, RpcError> {
+    let value = result.map_err(|report| RpcError::new(TRACKER_ERROR, format!("{report:?}")))?;
+    serde_json::to_value(value).map_err(|error| RpcError::new(TRACKER_ERROR, error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn dispatches_tracker_calls() {
+        let dir = TempDir::new().unwrap();
+        let (db, lockfile) = (dir.path().join("db.json"), dir.path().join("lock.json"));
+        let mut tracker = FlatFileTracker::new(&db, &lockfile).in_memory().unwrap();
+        let paths = (db.as_path(), lockfile.as_path());
+        let mut call = |method, params| dispatch(&mut tracker, method, &params, paths);
+
+        let started = call("start_with", json!({ "details": { "project": "web" } }));
+        assert_eq!(started, Ok(json!("Started")));
+        assert_eq!(call("is_running", json!({})), Ok(json!(true)));
+        assert_eq!(call("stop", json!({})), Ok(Value::Null));
+        let records = call("records", json!({})).unwrap();
+        assert_eq!(records[0]["project"], "web");
+        assert_eq!(
+            call("hello", json!({})).unwrap()["db"],
+            json!(db.to_str().unwrap())
+        );
+
+        assert_eq!(
+            call("launch", json!({})).unwrap_err().code,
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            call("add_note", json!({})).unwrap_err().code,
+            INVALID_PARAMS
+        );
+        assert_eq!(call("stop", json!({})).unwrap_err().code, TRACKER_ERROR);
+    }
+}
@@ -0,0 +1,248 @@
+use std::io::{Read, Write};
+
+use chrono::{DateTime, Utc};
+use error_stack::{Report, Result, ResultExt};
+use serde::{Deserialize, Serialize};
+
+use super::{ExportError, Exporter};
+use crate::feature::{
+    report_fmt::{DurationFormat, HMSFormatter},
+    tracker::{Break, RecordId, TimeRecord},
+};
+
+// tags share a single column; separators and backslashes in a tag are escaped with a backslash
+const TAG_SEPARATOR: char = ';';
+const TAG_ESCAPE: char = '\\';
+
+#[derive(Debug, Default)]
+pub struct CsvExporter;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRow {
+    id: RecordId,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    duration_secs: u64,
+    duration_hms: String,
+    project: Option<String>,
+    tags: String,
+    note: Option<String>,
+    // missing in files written before the column existed
+    #[serde(default = "billable_default")]
+    billable: bool,
+    /// `start/end` pairs in RFC 3339, separated like tags
+    #[serde(default)]
+    breaks: String,
+    #[serde(default)]
+    task: Option<String>,
+    /// number of the invoice the record was billed on
+    #[serde(default)]
+    invoice: Option<u32>,
+}
+
+fn billable_default() -> bool {
+    true
+}
+
+impl From<&TimeRecord> for CsvRow {
+    fn from(record: &TimeRecord) -> Self {
+        let duration = record.duration();
+        Self {
+            id: record.id,
+            start: record.start.datetime(),
+            end: record.end.datetime(),
+            duration_secs: duration.as_secs(),
+            duration_hms: HMSFormatter.format(duration),
+            project: record.project.clone(),
+            tags: join_tags(&record.tags),
+            note: record.note.clone(),
+            billable: record.is_billable(),
+            breaks: record
+                .breaks
+                .iter()
+                .map(|pause| format!("{}/{}", pause.start.to_rfc3339(), pause.end.to_rfc3339()))
+                .collect::<Vec<_>>()
+                .join(&TAG_SEPARATOR.to_string()),
+            task: record.task.clone(),
+            invoice: record.invoice,
+        }
+    }
+}
+
+impl TryFrom<CsvRow> for TimeRecord {
+    type Error = Report<ExportError>;
+
+    fn try_from(row: CsvRow) -> std::result::Result<Self, Self::Error> {
+        let breaks = row
+            .breaks
+            .split(TAG_SEPARATOR)
+            .filter(|pause| !pause.is_empty())
+            .map(parse_break)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            id: row.id,
+            start: row.start.into(),
+            end: row.end.into(),
+            project: row.project,
+            tags: split_tags(&row.tags),
+            note: row.note,
+            breaks,
+            non_billable: !row.billable,
+            task: row.task,
+            invoice: row.invoice,
+        })
+    }
+}
+
+fn parse_break(text: &str) -> Result<Break, ExportError> {
+    let invalid = || format!("invalid break '{text}', expected start/end");
+    let (start, end) = text
+        .split_once('/')
+        .ok_or(ExportError)
+        .attach_printable_lazy(invalid)?;
+    let time = |text: &str| {
+        DateTime::parse_from_rfc3339(text)
+            .map(|time| time.with_timezone(&Utc))
+            .change_context(ExportError)
+            .attach_printable_lazy(invalid)
+    };
+    Ok(Break {
+        start: time(start)?,
+        end: time(end)?,
+    })
+}
+
+fn join_tags(tags: &[String]) -> String {
+    let escaped: Vec<_> = tags
+        .iter()
+        .map(|tag| tag.replace(TAG_ESCAPE, r"\\").replace(TAG_SEPARATOR, r"\;"))
+        .collect();
+    escaped.join(&TAG_SEPARATOR.to_string())
+}
+
+fn split_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut tag = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            TAG_ESCAPE => tag.extend(chars.next()),
+            TAG_SEPARATOR => tags.push(std::mem::take(&mut tag)),
+            c => tag.push(c),
+        }
+    }
+    tags.push(tag);
+    tags.retain(|tag| !tag.is_empty());
+    tags
+}
+
+impl Exporter for CsvExporter {
+    fn export(&self, records: &[TimeRecord], out: &mut dyn Write) -> Result<(), ExportError> {
+        let mut writer = ::csv::Writer::from_writer(out);
+        for record in records {
+            writer
+                .serialize(CsvRow::from(record))
+                .change_context(ExportError)
+                .attach_printable("failed to write csv row")?;
+        }
+        writer
+            .flush()
+            .change_context(ExportError)
+            .attach_printable("failed to flush csv output")?;
+        Ok(())
+    }
+}
+
+/// Reads records back from the output of [`CsvExporter`]; the duration columns are ignored.
+pub fn read_records<R: Read>(input: R) -> Result<Vec<TimeRecord>, ExportError> {
+    ::csv::Reader::from_reader(input)
+        .deserialize::<CsvRow>()
+        .map(|row| {
+            row.change_context(ExportError)
+                .attach_printable("failed to read csv row")
+                .and_then(TimeRecord::try_from)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::feature::tracker::SessionDetails;
+
+    fn record() -> TimeRecord {
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 10, 18, 10, 30, 5).unwrap();
+        TimeRecord::new(
+            start.into(),
+            end.into(),
+            SessionDetails {
+                project: Some("acme".to_string()),
+                tags: vec!["review".to_string(), "billable".to_string()],
+                note: Some("PR 42, \"quoted\"".to_string()),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn writes_header_and_duration_columns() {
+        let record = record();
+        let mut out = Vec::new();
+        CsvExporter
+            .export(std::slice::from_ref(&record), &mut out)
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next(),
+            Some("id,start,end,duration_secs,duration_hms,project,tags,note,billable,breaks,task,invoice")
+        );
+        let row = lines.next().unwrap();
+        assert!(row.starts_with(&record.id.to_string()));
+        assert!(row.contains(",5405,01:30:05,acme,review;billable,\"PR 42, \"\"quoted\"\"\""));
+    }
+
+    #[test]
+    fn reads_back_exported_records() {
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 9, 30, 0).unwrap();
+        let pauses = vec![
+            Break {
+                start,
+                end: start + chrono::Duration::minutes(10),
+            },
+            Break {
+                start: start + chrono::Duration::minutes(20),
+                end: start + chrono::Duration::minutes(25),
+            },
+        ];
+        let mut billed = record();
+        billed.task = Some("login".to_string());
+        billed.invoice = Some(3);
+        let records = vec![record(), record().with_breaks(pauses), billed];
+        let mut out = Vec::new();
+        CsvExporter.export(&records, &mut out).unwrap();
+
+        let parsed = read_records(out.as_slice()).unwrap();
+
+        assert_eq!(parsed, records);
+    }
+
+    #[test]
+    fn tags_with_separators_round_trip() {
+        let mut record = record();
+        record.tags = vec!["a;b".to_string(), r"c\".to_string(), "d".to_string()];
+        let mut out = Vec::new();
+        CsvExporter
+            .export(std::slice::from_ref(&record), &mut out)
+            .unwrap();
+
+        assert!(String::from_utf8(out.clone())
+            .unwrap()
+            .contains(r",a\;b;c\\;d,"));
+        assert_eq!(read_records(out.as_slice()).unwrap(), vec![record]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::Write;
+
+use chrono::{DateTime, Utc};
+use error_stack::{Result, ResultExt};
+
+use super::{ExportError, Exporter};
+use crate::feature::tracker::TimeRecord;
+
+const PRODID: &str = "-//track//time tracker//EN";
+const UNTITLED_SUMMARY: &str = "Tracked time";
+// RFC 5545 limits content lines to 75 octets, excluding the line break
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Debug)]
+pub struct IcsExporter {
+    /// written as the `DTSTAMP` of every event
+    pub exported_at: DateTime<Utc>,
+}
+
+impl Exporter for IcsExporter {
+    fn export(&self, records: &[TimeRecord], out: &mut dyn Write) -> Result<(), ExportError> {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            format!("PRODID:{PRODID}"),
+            "CALSCALE:GREGORIAN".to_string(),
+        ];
+        for record in records {
+            lines.extend(event_lines(record, self.exported_at));
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        for line in lines {
+            out.write_all(fold(&line).as_bytes())
+                .change_context(ExportError)
+                .attach_printable("failed to write calendar line")?;
+        }
+        Ok(())
+    }
+}
+
+fn event_lines(record: &TimeRecord, exported_at: DateTime<Utc>) -> Vec<String> {
+    let summary = record.project.as_deref().unwrap_or(UNTITLED_SUMMARY);
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}@track", record.id),
+        // records can be edited and invoiced later, and nothing keeps the time of that, so
+        // every export is stamped as new information for calendar clients
+        format!("DTSTAMP:{}", utc_stamp(exported_at)),
+        format!("DTSTART:{}", utc_stamp(record.start.datetime())),
+        format!("DTEND:{}", utc_stamp(record.end.datetime())),
+        format!("SUMMARY:{}", escape_text(summary)),
+    ];
+    if !record.tags.is_empty() {
+        let categories: Vec<_> = record.tags.iter().map(|tag| escape_text(tag)).collect();
+        lines.push(format!("CATEGORIES:{}", categories.join(",")));
+    }
+    if let Some(note) = &record.note {
+        lines.push(format!("DESCRIPTION:{}", escape_text(note)));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+fn utc_stamp(datetime: DateTime<Utc>) -> String {
+    datetime.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Splits a content line into CRLF-terminated chunks of at most 75 octets.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // the leading space of a continuation line counts towards its length
+            width = 1;
+        }
+        folded.push(ch);
+        width += ch.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::feature::tracker::SessionDetails;
+
+    fn record(details: SessionDetails) -> TimeRecord {
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 10, 18, 10, 30, 0).unwrap();
+        TimeRecord::new(start.into(), end.into(), details)
+    }
+
+    fn export(records: &[TimeRecord]) -> String {
+        let exporter = IcsExporter {
+            exported_at: Utc.with_ymd_and_hms(2026, 10, 19, 8, 0, 0).unwrap(),
+        };
+        let mut out = Vec::new();
+        exporter.export(records, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn emits_one_event_per_record_in_utc() {
+        let record = record(SessionDetails {
+            project: Some("acme".to_string()),
+            tags: vec!["review".to_string(), "on,site".to_string()],
+            ..Default::default()
+        });
+
+        let text = export(std::slice::from_ref(&record));
+
+        assert!(text.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(text.contains(&format!("UID:{}@track\r\n", record.id)));
+        assert!(text.contains("DTSTAMP:20261019T080000Z\r\n"));
+        assert!(text.contains("DTSTART:20261018T090000Z\r\n"));
+        assert!(text.contains("DTEND:20261018T103000Z\r\n"));
+        assert!(text.contains("SUMMARY:acme\r\n"));
+        assert!(text.contains("CATEGORIES:review,on\\,site\r\n"));
+        assert!(text.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn uid_is_stable_across_exports() {
+        let record = record(SessionDetails::default());
+
+        let first = export(std::slice::from_ref(&record));
+        let second = export(std::slice::from_ref(&record));
+
+        assert_eq!(first, second);
+        assert!(first.contains("SUMMARY:Tracked time\r\n"));
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let folded = fold(&format!("DESCRIPTION:{}", "x".repeat(100)));
+
+        let lines: Vec<_> = folded.split("\r\n").collect();
+        assert_eq!(lines[0].len(), MAX_LINE_OCTETS);
+        assert!(lines[1].starts_with(' '));
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE_OCTETS));
+    }
+}
@@ -0,0 +1,46 @@
+use std::io::Write;
+
+use chrono::Utc;
+use chrono_tz::Tz;
+use error_stack::Result;
+
+use super::tracker::{reporter::GroupBy, TimeRecord};
+
+pub mod csv;
+pub mod ics;
+pub mod org;
+pub mod timeclock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    /// iCalendar, one event per record
+    Ics,
+    /// ledger/hledger timeclock, project as account and note as description
+    Timeclock,
+    /// Emacs Org headings with CLOCK lines
+    Org,
+}
+
+impl ExportFormat {
+    /// `tz` is the zone of formats that hold wall-clock times without an offset, and of
+    /// the days org output is grouped by.
+    pub fn exporter(&self, group_by: GroupBy, tz: Tz) -> Box<dyn Exporter> {
+        match self {
+            ExportFormat::Csv => Box::new(csv::CsvExporter),
+            ExportFormat::Ics => Box::new(ics::IcsExporter {
+                exported_at: Utc::now(),
+            }),
+            ExportFormat::Timeclock => Box::new(timeclock::TimeclockExporter { tz }),
+            ExportFormat::Org => Box::new(org::OrgExporter { group_by, tz }),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("export error")]
+pub struct ExportError;
+
+pub trait Exporter {
+    fn export(&self, records: &[TimeRecord], out: &mut dyn Write) -> Result<(), ExportError>;
+}
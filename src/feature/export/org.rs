@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, io::Write};
+
+use chrono::{DateTime, DurationRound, NaiveDate, TimeDelta, Utc};
+use chrono_tz::Tz;
+use error_stack::{Result, ResultExt};
+
+use super::{ExportError, Exporter};
+use crate::feature::{
+    report_fmt::{DurationFormat, OrgClockFormatter},
+    tracker::{reporter::GroupBy, TimeRecord},
+};
+
+const NO_PROJECT_HEADING: &str = "Unassigned";
+
+#[derive(Debug)]
+pub struct OrgExporter {
+    pub group_by: GroupBy,
+    /// zone of the days and clock stamps
+    pub tz: Tz,
+}
+
+impl Exporter for OrgExporter {
+    fn export(&self, records: &[TimeRecord], out: &mut dyn Write) -> Result<(), ExportError> {
+        let mut records: Vec<_> = records.iter().collect();
+        records.sort_by_key(|rec| rec.start.timestamp_millis());
+
+        let mut text = String::new();
+        match self.group_by {
+            GroupBy::Project => {
+                for (project, records) in by_project(records) {
+                    text.push_str(&format!("* {project}\n"));
+                    push_logbook(&mut text, &records, 2, &self.tz);
+                }
+            }
+            GroupBy::Day => {
+                let mut days: BTreeMap<NaiveDate, Vec<&TimeRecord>> = BTreeMap::new();
+                for record in records {
+                    let day = record.start.datetime().with_timezone(&self.tz).date_naive();
+                    days.entry(day).or_default().push(record);
+                }
+                for (day, records) in days {
+                    text.push_str(&format!("* {}\n", day.format("%Y-%m-%d %a")));
+                    for (project, records) in by_project(records) {
+                        text.push_str(&format!("** {project}\n"));
+                        push_logbook(&mut text, &records, 3, &self.tz);
+                    }
+                }
+            }
+        }
+        out.write_all(text.as_bytes())
+            .change_context(ExportError)
+            .attach_printable("failed to write org output")
+    }
+}
+
+fn by_project(records: Vec<&TimeRecord>) -> BTreeMap<&str, Vec<&TimeRecord>> {
+    let mut projects: BTreeMap<&str, Vec<&TimeRecord>> = BTreeMap::new();
+    for record in records {
+        let project = record.project.as_deref().unwrap_or(NO_PROJECT_HEADING);
+        projects.entry(project).or_default().push(record);
+    }
+    projects
+}
+
+fn push_logbook(text: &mut String, records: &[&TimeRecord], indent: usize, tz: &Tz) {
+    let indent = " ".repeat(indent);
+    text.push_str(&format!("{indent}:LOGBOOK:\n"));
+    for record in records {
+        // one clock line per stretch between breaks, so clocktables leave them out
+        for (start, end) in record.work_spans() {
+            text.push_str(&format!("{indent}{}\n", clock_line(start, end, tz)));
+        }
+    }
+    text.push_str(&format!("{indent}:END:\n"));
+}
+
+fn clock_line(start: DateTime<Utc>, end: DateTime<Utc>, tz: &Tz) -> String {
+    // Org only keeps minutes, so the duration is taken between the truncated stamps
+    let start = to_minute(start);
+    let end = to_minute(end);
+    let duration = (end - start).to_std().unwrap_or_default();
+    format!(
+        "CLOCK: [{}]--[{}] => {}",
+        org_stamp(start, tz),
+        org_stamp(end, tz),
+        OrgClockFormatter.format(duration)
+    )
+}
+
+fn to_minute(datetime: DateTime<Utc>) -> DateTime<Utc> {
+    datetime
+        .duration_trunc(TimeDelta::minutes(1))
+        .unwrap_or(datetime)
+}
+
+fn org_stamp(datetime: DateTime<Utc>, tz: &Tz) -> String {
+    datetime
+        .with_timezone(tz)
+        .format("%Y-%m-%d %a %H:%M")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::feature::tracker::{Break, SessionDetails};
+
+    fn record(day: u32, hour: u32, minutes: i64, project: Option<&str>) -> TimeRecord {
+        let start = Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+        let end = start + TimeDelta::minutes(minutes);
+        let details = SessionDetails {
+            project: project.map(str::to_string),
+            ..Default::default()
+        };
+        TimeRecord::new(start.into(), end.into(), details)
+    }
+
+    /// The clock line of a record without breaks.
+    fn line(record: &TimeRecord, tz: &Tz) -> String {
+        clock_line(record.start.datetime(), record.end.datetime(), tz)
+    }
+
+    fn export(group_by: GroupBy, records: &[TimeRecord]) -> String {
+        let mut out = Vec::new();
+        OrgExporter {
+            group_by,
+            tz: chrono_tz::Europe::Berlin,
+        }
+        .export(records, &mut out)
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn groups_clock_lines_under_project_headings() {
+        let records = [
+            record(18, 9, 90, Some("acme")),
+            record(18, 13, 30, None),
+            record(19, 9, 605, Some("acme")),
+        ];
+
+        let text = export(GroupBy::Project, &records);
+
+        let expected = format!(
+            "* Unassigned\n  :LOGBOOK:\n  {}\n  :END:\n\
+             * acme\n  :LOGBOOK:\n  {}\n  {}\n  :END:\n",
+            line(&records[1], &chrono_tz::Europe::Berlin),
+            line(&records[0], &chrono_tz::Europe::Berlin),
+            line(&records[2], &chrono_tz::Europe::Berlin),
+        );
+        assert_eq!(text, expected);
+        assert_eq!(
+            line(&records[0], &chrono_tz::Europe::Berlin),
+            "CLOCK: [2026-10-18 Sun 11:00]--[2026-10-18 Sun 12:30] =>  1:30"
+        );
+        assert!(line(&records[2], &Tz::UTC).ends_with("] => 10:05"));
+    }
+
+    #[test]
+    fn groups_projects_under_day_headings() {
+        let records = [
+            record(18, 9, 90, Some("acme")),
+            record(18, 11, 15, Some("beta")),
+            // already the next day in Berlin
+            record(18, 23, 15, Some("beta")),
+        ];
+
+        let text = export(GroupBy::Day, &records);
+
+        assert!(text.starts_with("* 2026-10-18 Sun\n** acme\n"));
+        assert!(text.contains("** beta\n   :LOGBOOK:\n   CLOCK: ["));
+        assert!(text.contains("* 2026-10-19 Mon\n** beta\n"));
+    }
+
+    #[test]
+    fn breaks_split_the_clock_lines() {
+        let record = record(18, 9, 120, Some("acme")).with_breaks(vec![Break {
+            start: Utc.with_ymd_and_hms(2026, 10, 18, 9, 30, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2026, 10, 18, 9, 45, 0).unwrap(),
+        }]);
+
+        let text = export(GroupBy::Project, &[record]);
+
+        assert_eq!(
+            text,
+            "* acme\n  :LOGBOOK:\n\
+             \x20 CLOCK: [2026-10-18 Sun 11:00]--[2026-10-18 Sun 11:30] =>  0:30\n\
+             \x20 CLOCK: [2026-10-18 Sun 11:45]--[2026-10-18 Sun 13:00] =>  1:15\n\
+             \x20 :END:\n"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::io::Write;
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use error_stack::{Result, ResultExt};
+
+use super::{ExportError, Exporter};
+use crate::feature::tracker::TimeRecord;
+
+/// Timeclock files hold local wall-clock times without an offset.
+pub const TIMESTAMP_FORMAT: &str = "%Y/%m/%d %H:%M:%S";
+/// Account for records that have no project.
+pub const DEFAULT_ACCOUNT: &str = "track";
+
+#[derive(Debug)]
+pub struct TimeclockExporter {
+    /// zone of the written wall-clock times
+    pub tz: Tz,
+}
+
+impl Exporter for TimeclockExporter {
+    fn export(&self, records: &[TimeRecord], out: &mut dyn Write) -> Result<(), ExportError> {
+        let mut records: Vec<_> = records.iter().collect();
+        records.sort_by_key(|rec| rec.start.timestamp_millis());
+
+        for record in records {
+            let account = record.project.as_deref().unwrap_or(DEFAULT_ACCOUNT);
+            // breaks are left out by clocking out and in again around them
+            for (start, end) in record.work_spans() {
+                let mut clock_in = format!("i {} {account}", local_stamp(start, &self.tz));
+                if let Some(note) = &record.note {
+                    // two spaces separate the account from the description
+                    clock_in.push_str("  ");
+                    clock_in.push_str(&note.replace('\n', " "));
+                }
+                writeln!(out, "{clock_in}")
+                    .and_then(|_| writeln!(out, "o {}", local_stamp(end, &self.tz)))
+                    .change_context(ExportError)
+                    .attach_printable("failed to write timeclock entry")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn local_stamp(datetime: DateTime<Utc>, tz: &Tz) -> String {
+    datetime
+        .with_timezone(tz)
+        .format(TIMESTAMP_FORMAT)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::feature::tracker::{Break, SessionDetails};
+
+    #[test]
+    fn writes_clock_in_and_out_pairs_in_the_given_timezone() {
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 10, 18, 10, 30, 0).unwrap();
+        let details = SessionDetails {
+            project: Some("client:acme".to_string()),
+            note: Some("reviewing PR 42".to_string()),
+            ..Default::default()
+        };
+        let records = [
+            TimeRecord::new(start.into(), end.into(), details),
+            TimeRecord::new(
+                (start - chrono::Duration::hours(2)).into(),
+                (start - chrono::Duration::hours(1)).into(),
+                SessionDetails::default(),
+            ),
+        ];
+
+        let mut out = Vec::new();
+        TimeclockExporter {
+            tz: chrono_tz::Europe::Berlin,
+        }
+        .export(&records, &mut out)
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "i 2026/10/18 09:00:00 track\no 2026/10/18 10:00:00\n\
+             i 2026/10/18 11:00:00 client:acme  reviewing PR 42\no 2026/10/18 12:30:00\n"
+        );
+    }
+
+    #[test]
+    fn clocks_out_during_breaks() {
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        let record = TimeRecord::new(
+            start.into(),
+            (start + chrono::Duration::hours(2)).into(),
+            SessionDetails::default(),
+        )
+        .with_breaks(vec![Break {
+            start: start + chrono::Duration::minutes(30),
+            end: start + chrono::Duration::minutes(45),
+        }]);
+
+        let mut out = Vec::new();
+        TimeclockExporter { tz: chrono_tz::UTC }
+            .export(&[record], &mut out)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "i 2026/10/18 09:00:00 track\no 2026/10/18 09:30:00\n\
+             i 2026/10/18 09:45:00 track\no 2026/10/18 11:00:00\n"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use error_stack::{Report, Result, ResultExt};
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+
+use super::tracker::SessionDetails;
+
+/// Matches keys like `ABC-123`.
+const DEFAULT_ISSUE_PATTERN: &str = r"\b[A-Z][A-Z0-9]+-[0-9]+\b";
+
+/// Marks hooks written by `track git-hook install`, so they can be replaced safely.
+const HOOK_MARKER: &str = "# installed by track";
+
+#[derive(Debug, thiserror::Error)]
+#[error("git error")]
+pub struct GitError;
+
+/// The `git` section of the config: how a checkout becomes project and tags on `track start`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitSettings {
+    /// detect the checkout when starting without a project
+    pub enabled: bool,
+    /// project per repository name; other repositories use their name as project
+    pub projects: BTreeMap<String, String>,
+    /// tag sessions with the branch name
+    pub branch_tag: bool,
+    /// branches that are not worth a tag, such as `main`
+    pub ignore_branches: Vec<String>,
+    /// finds the issue key in a branch name, `null` to not tag issues
+    #[serde(deserialize_with = "deserialize_pattern")]
+    pub issue_pattern: Option<Regex>,
+}
+
+impl Default for GitSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            projects: BTreeMap::new(),
+            branch_tag: true,
+            ignore_branches: vec!["main".to_string(), "master".to_string()],
+            issue_pattern: Some(Regex::new(DEFAULT_ISSUE_PATTERN).expect("pattern is valid")),
+        }
+    }
+}
+
+fn deserialize_pattern<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Regex>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|pattern| Regex::new(&pattern).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+impl GitSettings {
+    /// Fills in the project, unless one was given, and adds branch and issue tags.
+    pub fn apply(&self, checkout: &Checkout, details: &mut SessionDetails) {
+        if details.project.is_none() {
+            let project = self.projects.get(&checkout.repo).unwrap_or(&checkout.repo);
+            details.project = Some(project.clone());
+        }
+        for tag in self.tags(checkout) {
+            if !details.tags.contains(&tag) {
+                details.tags.push(tag);
+            }
+        }
+    }
+
+    /// The branch and issue tags of `checkout`.
+    pub fn tags(&self, checkout: &Checkout) -> Vec<String> {
+        let Some(branch) = checkout
+            .branch
+            .as_ref()
+            .filter(|branch| !self.ignore_branches.contains(branch))
+        else {
+            return Vec::new();
+        };
+        let mut tags = Vec::new();
+        if self.branch_tag {
+            tags.push(branch.clone());
+        }
+        if let Some(issue) = self.issue(branch) {
+            tags.push(issue.to_string());
+        }
+        tags
+    }
+
+    /// The issue key in `branch`, e.g. `ABC-123` in `feature/ABC-123-login`.
+    pub fn issue<'a>(&self, branch: &'a str) -> Option<&'a str> {
+        Some(self.issue_pattern.as_ref()?.find(branch)?.as_str())
+    }
+}
+
+/// The repository and branch a directory is checked out at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkout {
+    /// name of the top-level directory
+    pub repo: String,
+    /// `None` when the head is detached
+    pub branch: Option<String>,
+}
+
+impl Checkout {
+    /// Asks git about `dir`; `None` outside a repository or without git installed.
+    pub fn detect(dir: &Path) -> Option<Self> {
+        let toplevel = git(dir, &["rev-parse", "--show-toplevel"])?;
+        let repo = Path::new(&toplevel)
+            .file_name()?
+            .to_string_lossy()
+            .into_owned();
+        let branch = git(dir, &["symbolic-ref", "--quiet", "--short", "HEAD"]);
+        Some(Self { repo, branch })
+    }
+
+    /// The checkout before the last branch switch, as recorded in the reflog; its branch is
+    /// `None` if that head was detached.
+    pub fn detect_previous(dir: &Path) -> Option<Self> {
+        let current = Self::detect(dir)?;
+        let name = git(dir, &["rev-parse", "--symbolic-full-name", "@{-1}"]);
+        let branch = name.and_then(|name| Some(name.strip_prefix("refs/heads/")?.to_string()));
+        Some(Self { branch, ..current })
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
+/// Writes a `post-checkout` hook into the repository at `dir` that runs
+/// `track git-hook post-checkout` with the given binary.
+///
+/// A hook that was not installed by track is only replaced with `force`.
+pub fn install_hook(dir: &Path, track: &Path, force: bool) -> Result<PathBuf, GitError> {
+    let hooks = git(dir, &["rev-parse", "--git-path", "hooks"])
+        .ok_or(GitError)
+        .attach_printable_lazy(|| format!("{} is not in a git repository", dir.display()))?;
+    let hooks = dir.join(hooks);
+    let path = hooks.join("post-checkout");
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !force && !existing.contains(HOOK_MARKER) {
+            return Err(Report::new(GitError)
+                .attach_printable(format!("{} already exists", path.display())));
+        }
+    }
+    fs::create_dir_all(&hooks)
+        .change_context(GitError)
+        .attach_printable_lazy(|| format!("unable to create {}", hooks.display()))?;
+    let script = format!(
+        "#!/bin/sh\n{HOOK_MARKER}: restarts the running session on the new branch\n\
+         exec '{}' git-hook post-checkout \"$@\"\n",
+        track.display().to_string().replace('\'', r"'\''")
+    );
+    fs::write(&path, script)
+        .change_context(GitError)
+        .attach_printable_lazy(|| format!("unable to write {}", path.display()))?;
+    make_executable(&path)?;
+    Ok(path)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), GitError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .change_context(GitError)
+        .attach_printable_lazy(|| format!("unable to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), GitError> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkout(branch: Option<&str>) -> Checkout {
+        Checkout {
+            repo: "track-rs".to_string(),
+            branch: branch.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn finds_issue_keys_in_branch_names() {
+        let settings = GitSettings::default();
+
+        assert_eq!(settings.issue("feature/ABC-123-login"), Some("ABC-123"));
+        assert_eq!(settings.issue("fix/JIRA2-7"), Some("JIRA2-7"));
+        assert_eq!(settings.issue("feature/login"), None);
+    }
+
+    #[test]
+    fn adds_project_branch_and_issue() {
+        let settings: GitSettings =
+            serde_json::from_str(r#"{"enabled":true,"projects":{"track-rs":"track"}}"#).unwrap();
+        let mut details = SessionDetails {
+            tags: vec!["ABC-123".to_string()],
+            ..Default::default()
+        };
+
+        settings.apply(&checkout(Some("feature/ABC-123-login")), &mut details);
+
+        assert_eq!(details.project.as_deref(), Some("track"));
+        assert_eq!(details.tags, ["ABC-123", "feature/ABC-123-login"]);
+    }
+
+    #[test]
+    fn keeps_a_given_project_and_skips_ignored_branches() {
+        let settings: GitSettings =
+            serde_json::from_str(r#"{"branch_tag":false,"issue_pattern":null}"#).unwrap();
+        let mut details = SessionDetails {
+            project: Some("web".to_string()),
+            ..Default::default()
+        };
+
+        settings.apply(&checkout(Some("feature/ABC-1")), &mut details);
+        settings.apply(&checkout(Some("main")), &mut details);
+
+        assert_eq!(details.project.as_deref(), Some("web"));
+        assert!(details.tags.is_empty());
+    }
+}
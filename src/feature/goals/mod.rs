@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use owo_colors::OwoColorize;
+
+use super::{
+    report_fmt::{DurationFormat, HMSFormatter},
+    tracker::reporter::{GoalProgress, Period},
+};
+
+const BAR_WIDTH: usize = 20;
+const LABEL_WIDTH: usize = 20;
+
+/// Renders one line per goal with a progress bar, the remaining time and a projection.
+///
+/// Daily goals show when the target is reached if the running session keeps going; weekly
+/// goals show the total the week is on pace for.
+pub fn render(progress: &[GoalProgress], now: DateTime<Utc>, tz: &Tz) -> String {
+    let mut text = String::new();
+    for goal in progress {
+        let status = if goal.remaining().is_zero() {
+            let over = goal.done - goal.goal.target;
+            format!("{} +{}", "reached".green(), HMSFormatter.format(over))
+        } else {
+            format!("{} left", HMSFormatter.format(goal.remaining()))
+        };
+        let projection = match (goal.goal.period, goal.finish_at(now)) {
+            (Period::Day, Some(finish)) => {
+                format!("  done at {}", finish.with_timezone(tz).format("%H:%M"))
+            }
+            (Period::Day, None) => String::new(),
+            (Period::Week | Period::Month, _) => {
+                format!("  on pace for {}", HMSFormatter.format(goal.projected(now)))
+            }
+        };
+        text.push_str(&format!(
+            "{:LABEL_WIDTH$} {} {:>4.0}%  {} / {}  {status}{projection}\n",
+            label(goal),
+            bar(goal.fraction()),
+            goal.fraction() * 100.0,
+            HMSFormatter.format(goal.done),
+            HMSFormatter.format(goal.goal.target),
+        ));
+    }
+    text
+}
+
+fn label(goal: &GoalProgress) -> String {
+    let period = match goal.goal.period {
+        Period::Day => "today",
+        Period::Week => "this week",
+        Period::Month => "this month",
+    };
+    let label = match &goal.goal.project {
+        Some(project) => format!("{project} {period}"),
+        None => period.to_string(),
+    };
+    label.chars().take(LABEL_WIDTH).collect()
+}
+
+fn bar(fraction: f64) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let done = "█".repeat(filled);
+    let done = match fraction >= 1.0 {
+        true => done.green().to_string(),
+        false => done,
+    };
+    format!("[{done}{}]", "░".repeat(BAR_WIDTH - filled).dimmed())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::feature::tracker::reporter::Goal;
+
+    fn progress(period: Period, target: u64, done: u64, running: bool) -> GoalProgress {
+        let from = Utc.with_ymd_and_hms(2026, 10, 19, 0, 0, 0).unwrap();
+        let days = if period == Period::Day { 1 } else { 7 };
+        GoalProgress {
+            goal: Goal {
+                period,
+                target: Duration::from_secs(target * 3600),
+                project: None,
+                days: None,
+            },
+            from,
+            to: from + chrono::Duration::days(days),
+            done: Duration::from_secs(done * 3600),
+            running,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn daily_goal_shows_finish_time_while_running() {
+        let text = render(&[progress(Period::Day, 8, 2, true)], now(), &Tz::UTC);
+
+        assert!(text.starts_with("today "));
+        assert!(text.contains("25%  02:00:00 / 08:00:00  06:00:00 left  done at 18:00"));
+    }
+
+    #[test]
+    fn weekly_goal_shows_pace() {
+        let text = render(&[progress(Period::Week, 40, 1, false)], now(), &Tz::UTC);
+
+        // half a day into the week, one hour a half day is 14 hours a week
+        assert!(text.contains("on pace for 14:00:00"));
+    }
+
+    #[test]
+    fn reached_goal_shows_overtime() {
+        let text = render(&[progress(Period::Day, 8, 9, false)], now(), &Tz::UTC);
+
+        assert!(text.contains("+01:00:00"));
+        assert!(!text.contains("done at"));
+    }
+}
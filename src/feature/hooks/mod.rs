@@ -0,0 +1,429 @@
+use std::{
+    collections::BTreeSet,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use error_stack::{Report, Result, ResultExt};
+use serde::Serialize;
+
+use super::tracker::{
+    reporter::Reporter, ActiveSession, PauseStatus, RecordId, ResumeStatus, SessionDetails,
+    StartupStatus, Task, TimeRecord, Tracker, TrackerError,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("hook error")]
+pub struct HookError;
+
+/// What a hook is run for; the hook is the executable of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    PreStart,
+    PostStart,
+    PreStop,
+    PostStop,
+    PrePause,
+    PostPause,
+    PreResume,
+    PostResume,
+    /// before the running session is replaced by one with other details, e.g. on a branch
+    /// checkout; gets the running session
+    PreSwitch,
+    /// after the replacement; gets the new session
+    PostSwitch,
+}
+
+impl Event {
+    pub fn name(self) -> &'static str {
+        match self {
+            Event::PreStart => "pre-start",
+            Event::PostStart => "post-start",
+            Event::PreStop => "pre-stop",
+            Event::PostStop => "post-stop",
+            Event::PrePause => "pre-pause",
+            Event::PostPause => "post-pause",
+            Event::PreResume => "pre-resume",
+            Event::PostResume => "post-resume",
+            Event::PreSwitch => "pre-switch",
+            Event::PostSwitch => "post-switch",
+        }
+    }
+
+    fn is_pre(self) -> bool {
+        self.name().starts_with("pre-")
+    }
+}
+
+/// The session a hook is run for, written as JSON to its stdin.
+#[derive(Debug, Clone, Serialize)]
+pub struct Payload<'a> {
+    pub event: &'static str,
+    pub timer: &'a str,
+    pub start: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub details: &'a SessionDetails,
+}
+
+impl Payload<'_> {
+    fn env(&self) -> Vec<(&'static str, String)> {
+        let mut env = vec![
+            ("TRACK_EVENT", self.event.to_string()),
+            ("TRACK_TIMER", self.timer.to_string()),
+            ("TRACK_START", self.start.to_rfc3339()),
+            ("TRACK_TAGS", self.details.tags.join(",")),
+        ];
+        let optional = [
+            ("TRACK_PROJECT", self.details.project.clone()),
+            ("TRACK_NOTE", self.details.note.clone()),
+            ("TRACK_TASK", self.details.task.clone()),
+            ("TRACK_END", self.end.map(|end| end.to_rfc3339())),
+        ];
+        env.extend(
+            optional
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, value?))),
+        );
+        env
+    }
+}
+
+/// Executables in a directory, such as `~/.config/track/hooks/`, named after events.
+#[derive(Debug, Clone, Default)]
+pub struct Hooks {
+    dir: Option<PathBuf>,
+}
+
+impl Hooks {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    /// Runs no hooks at all.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Runs the hook of `event`, if there is one.
+    ///
+    /// The session is passed as `TRACK_*` environment variables and as JSON on stdin. Fails
+    /// if the hook cannot be run or exits unsuccessfully.
+    pub fn run(&self, event: Event, payload: &Payload) -> Result<(), HookError> {
+        let Some(path) = self.dir.as_ref().map(|dir| dir.join(event.name())) else {
+            return Ok(());
+        };
+        if !path.is_file() {
+            return Ok(());
+        }
+        let json = serde_json::to_vec(payload)
+            .change_context(HookError)
+            .attach_printable("unable to serialize the session")?;
+        let mut child = Command::new(&path)
+            .envs(payload.env())
+            .stdin(Stdio::piped())
+            .spawn()
+            .change_context(HookError)
+            .attach_printable_lazy(|| format!("unable to run {}", path.display()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // a hook that ignores its input may exit before reading it
+            let _ = stdin.write_all(&json);
+        }
+        let status = child
+            .wait()
+            .change_context(HookError)
+            .attach_printable_lazy(|| format!("unable to wait for {}", path.display()))?;
+        if !status.success() {
+            return Err(Report::new(HookError)
+                .attach_printable(format!("{} exited with {status}", path.display())));
+        }
+        Ok(())
+    }
+}
+
+/// A tracker that runs hooks around starting, stopping, pausing, resuming and switching its
+/// timer.
+///
+/// A failing `pre-` hook aborts the operation; a failing `post-` hook is only logged, since
+/// the operation already happened.
+pub struct HookedTracker<T> {
+    inner: T,
+    hooks: Hooks,
+    timer: String,
+}
+
+impl<T: Tracker> Reporter for HookedTracker<T> {}
+
+impl<T: Tracker> HookedTracker<T> {
+    /// Wraps `inner`, which operates on the timer named `timer`.
+    pub fn new(inner: T, hooks: Hooks, timer: &str) -> Self {
+        Self {
+            inner,
+            hooks,
+            timer: timer.to_string(),
+        }
+    }
+
+    fn session(&self) -> Result<Option<ActiveSession>, TrackerError> {
+        Ok(self
+            .inner
+            .active_sessions()?
+            .into_iter()
+            .find(|session| session.timer == self.timer))
+    }
+
+    fn hook(&self, event: Event, session: &ActiveSession) -> Result<(), TrackerError> {
+        let payload = Payload {
+            event: event.name(),
+            timer: &self.timer,
+            start: session.start.datetime(),
+            end: None,
+            details: &session.details,
+        };
+        self.hook_with(event, &payload)
+    }
+
+    fn hook_with(&self, event: Event, payload: &Payload) -> Result<(), TrackerError> {
+        match self.hooks.run(event, payload) {
+            Ok(()) => Ok(()),
+            Err(report) if event.is_pre() => Err(report
+                .change_context(TrackerError)
+                .attach_printable(format!("{} hook failed", event.name()))),
+            Err(report) => {
+                tracing::warn!(hook = event.name(), "{report:?}");
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<T: Tracker> Tracker for HookedTracker<T> {
+    fn start_with(&mut self, details: SessionDetails) -> Result<StartupStatus, TrackerError> {
+        if self.inner.is_running()? {
+            return self.inner.start_with(details);
+        }
+        let payload = Payload {
+            event: Event::PreStart.name(),
+            timer: &self.timer,
+            start: Utc::now(),
+            end: None,
+            details: &details,
+        };
+        self.hook_with(Event::PreStart, &payload)?;
+        let status = self.inner.start_with(details)?;
+        if let Some(session) = self.session()? {
+            self.hook(Event::PostStart, &session)?;
+        }
+        Ok(status)
+    }
+
+    fn is_running(&self) -> Result<bool, TrackerError> {
+        self.inner.is_running()
+    }
+
+    fn active_sessions(&self) -> Result<Vec<ActiveSession>, TrackerError> {
+        self.inner.active_sessions()
+    }
+
+    fn stop(&mut self) -> Result<(), TrackerError> {
+        let Some(session) = self.session()? else {
+            return self.inner.stop();
+        };
+        self.hook(Event::PreStop, &session)?;
+        self.inner.stop()?;
+        let payload = Payload {
+            event: Event::PostStop.name(),
+            timer: &self.timer,
+            start: session.start.datetime(),
+            end: Some(Utc::now()),
+            details: &session.details,
+        };
+        self.hook_with(Event::PostStop, &payload)
+    }
+
+    /// Only the `pre-switch` and `post-switch` hooks run, so a failing hook either keeps the
+    /// old session or leaves the new one running, never neither.
+    fn switch(&mut self, details: SessionDetails) -> Result<StartupStatus, TrackerError> {
+        let Some(session) = self.session()? else {
+            return self.start_with(details);
+        };
+        self.hook(Event::PreSwitch, &session)?;
+        self.inner.stop()?;
+        let status = self
+            .inner
+            .start_with(details)
+            .attach_printable("the previous session was stopped")?;
+        if let Some(session) = self.session()? {
+            self.hook(Event::PostSwitch, &session)?;
+        }
+        Ok(status)
+    }
+
+    fn pause(&mut self) -> Result<PauseStatus, TrackerError> {
+        let session = self.session()?.filter(|session| !session.is_paused());
+        if let Some(session) = &session {
+            self.hook(Event::PrePause, session)?;
+        }
+        let status = self.inner.pause()?;
+        if let Some(session) = session.filter(|_| status == PauseStatus::Paused) {
+            self.hook(Event::PostPause, &session)?;
+        }
+        Ok(status)
+    }
+
+    fn resume(&mut self) -> Result<ResumeStatus, TrackerError> {
+        let session = self.session()?.filter(ActiveSession::is_paused);
+        if let Some(session) = &session {
+            self.hook(Event::PreResume, session)?;
+        }
+        let status = self.inner.resume()?;
+        if let Some(session) = session.filter(|_| status == ResumeStatus::Resumed) {
+            self.hook(Event::PostResume, &session)?;
+        }
+        Ok(status)
+    }
+
+    fn add_note(&mut self, note: &str) -> Result<(), TrackerError> {
+        self.inner.add_note(note)
+    }
+
+    fn records(&self) -> Result<impl Iterator<Item = TimeRecord>, TrackerError> {
+        self.inner.records()
+    }
+
+    fn add_records(&mut self, records: Vec<TimeRecord>) -> Result<(), TrackerError> {
+        self.inner.add_records(records)
+    }
+
+    fn update_record(&mut self, record: TimeRecord) -> Result<(), TrackerError> {
+        self.inner.update_record(record)
+    }
+
+    fn remove_record(&mut self, id: RecordId) -> Result<(), TrackerError> {
+        self.inner.remove_record(id)
+    }
+
+    fn days_off(&self) -> Result<BTreeSet<NaiveDate>, TrackerError> {
+        self.inner.days_off()
+    }
+
+    fn set_day_off(&mut self, date: NaiveDate, off: bool) -> Result<(), TrackerError> {
+        self.inner.set_day_off(date, off)
+    }
+
+    fn issue_invoice(&mut self, ids: &[RecordId]) -> Result<u32, TrackerError> {
+        self.inner.issue_invoice(ids)
+    }
+
+    fn tasks(&self) -> Result<Vec<Task>, TrackerError> {
+        self.inner.tasks()
+    }
+
+    fn save_task(&mut self, task: Task) -> Result<(), TrackerError> {
+        self.inner.save_task(task)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, os::unix::fs::PermissionsExt, path::Path};
+
+    use assert_fs::TempDir;
+
+    use super::*;
+    use crate::feature::tracker::flatfile::FlatFileTracker;
+
+    fn script(dir: &Path, event: Event, body: &str) {
+        let path = dir.join(event.name());
+        fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn tracker(dir: &TempDir) -> HookedTracker<FlatFileTracker> {
+        let inner = FlatFileTracker::new(dir.path().join("db.json"), dir.path().join("lock.json"));
+        HookedTracker::new(inner, Hooks::new(dir.path()), "default")
+    }
+
+    #[test]
+    fn hooks_get_the_session_as_env_and_json() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("out");
+        script(
+            dir.path(),
+            Event::PostStop,
+            &format!(
+                "echo \"$TRACK_EVENT $TRACK_PROJECT $TRACK_TAGS\" > {0}; cat >> {0}",
+                out.display()
+            ),
+        );
+        let mut tracker = tracker(&dir);
+        let details = SessionDetails {
+            project: Some("web".to_string()),
+            tags: vec!["a".to_string(), "b".to_string()],
+            ..Default::default()
+        };
+
+        tracker.start_with(details).unwrap();
+        tracker.stop().unwrap();
+
+        let out = fs::read_to_string(out).unwrap();
+        let (env, json) = out.split_once('\n').unwrap();
+        assert_eq!(env, "post-stop web a,b");
+        let json: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(json["timer"], "default");
+        assert_eq!(json["project"], "web");
+        assert!(json["end"].is_string());
+    }
+
+    #[test]
+    fn failing_pre_hook_aborts_and_failing_post_hook_does_not() {
+        let dir = TempDir::new().unwrap();
+        script(dir.path(), Event::PreStart, "exit 1");
+        script(dir.path(), Event::PostStop, "exit 1");
+        let mut tracker = tracker(&dir);
+
+        assert!(tracker.start().is_err());
+        assert!(!tracker.is_running().unwrap());
+
+        fs::remove_file(dir.path().join(Event::PreStart.name())).unwrap();
+        tracker.start().unwrap();
+        tracker.stop().unwrap();
+        assert_eq!(tracker.records().unwrap().count(), 1);
+    }
+
+    #[test]
+    fn switching_runs_only_the_switch_hooks() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("out");
+        for event in [Event::PreStop, Event::PreStart, Event::PreSwitch] {
+            script(dir.path(), event, "exit 1");
+        }
+        script(
+            dir.path(),
+            Event::PostSwitch,
+            &format!("echo \"$TRACK_PROJECT\" > {}", out.display()),
+        );
+        let mut tracker = tracker(&dir);
+        fs::remove_file(dir.path().join(Event::PreStart.name())).unwrap();
+        tracker.start().unwrap();
+        let details = SessionDetails {
+            project: Some("api".to_string()),
+            ..Default::default()
+        };
+
+        assert!(tracker.switch(details.clone()).is_err());
+        assert!(tracker.is_running().unwrap());
+        assert_eq!(tracker.records().unwrap().count(), 0);
+
+        fs::remove_file(dir.path().join(Event::PreSwitch.name())).unwrap();
+        tracker.switch(details).unwrap();
+
+        assert_eq!(tracker.records().unwrap().count(), 1);
+        assert_eq!(fs::read_to_string(out).unwrap(), "api\n");
+    }
+}
@@ -0,0 +1,110 @@
+use std::{collections::HashSet, io::Read};
+
+use chrono_tz::Tz;
+use error_stack::Result;
+
+use super::tracker::TimeRecord;
+
+pub mod timeclock;
+pub mod timewarrior;
+pub mod toggl;
+pub mod watson;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportSource {
+    /// JSON from `timew export`; the first tag becomes the project
+    Timewarrior,
+    /// JSON from `watson log --json`
+    Watson,
+    /// detailed report CSV from Toggl Track, times in the configured timezone
+    TogglCsv,
+    /// ledger/hledger timeclock file, account as project and description as note
+    Timeclock,
+}
+
+impl ImportSource {
+    /// `tz` is the zone of wall-clock times in Toggl and timeclock files.
+    pub fn parse<R: Read>(&self, input: R, tz: &Tz) -> Result<Vec<TimeRecord>, ImportError> {
+        match self {
+            ImportSource::Timewarrior => timewarrior::parse(input),
+            ImportSource::Watson => watson::parse(input),
+            ImportSource::TogglCsv => toggl::parse(input, tz),
+            ImportSource::Timeclock => timeclock::parse(input, tz),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("import error")]
+pub struct ImportError;
+
+/// Incoming records split into the ones to merge and the ones already tracked.
+#[derive(Debug, Default)]
+pub struct ImportPlan {
+    pub new: Vec<TimeRecord>,
+    pub duplicates: usize,
+}
+
+impl ImportPlan {
+    /// Records are duplicates when start, end (to the second) and project match.
+    pub fn new<I>(existing: I, incoming: Vec<TimeRecord>) -> Self
+    where
+        I: IntoIterator<Item = TimeRecord>,
+    {
+        let mut seen: HashSet<_> = existing.into_iter().map(|rec| dedupe_key(&rec)).collect();
+        let mut plan = Self::default();
+        for record in incoming {
+            if seen.insert(dedupe_key(&record)) {
+                plan.new.push(record);
+            } else {
+                plan.duplicates += 1;
+            }
+        }
+        plan
+    }
+}
+
+fn dedupe_key(record: &TimeRecord) -> (i64, i64, Option<String>) {
+    (
+        record.start.datetime().timestamp(),
+        record.end.datetime().timestamp(),
+        record.project.clone(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::feature::tracker::SessionDetails;
+
+    fn record(hour: u32, project: &str) -> TimeRecord {
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, hour, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 10, 18, hour, 45, 0).unwrap();
+        let details = SessionDetails {
+            project: Some(project.to_string()),
+            ..Default::default()
+        };
+        TimeRecord::new(start.into(), end.into(), details)
+    }
+
+    #[test]
+    fn skips_records_already_tracked() {
+        let existing = vec![record(9, "acme")];
+        let incoming = vec![record(9, "acme"), record(9, "globex"), record(10, "acme")];
+
+        let plan = ImportPlan::new(existing, incoming);
+
+        assert_eq!(plan.duplicates, 1);
+        assert_eq!(plan.new.len(), 2);
+    }
+
+    #[test]
+    fn skips_duplicates_within_the_import() {
+        let plan = ImportPlan::new(vec![], vec![record(9, "acme"), record(9, "acme")]);
+
+        assert_eq!(plan.duplicates, 1);
+        assert_eq!(plan.new.len(), 1);
+    }
+}
@@ -0,0 +1,169 @@
+use std::io::{BufRead, BufReader, Read};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use error_stack::{Report, Result, ResultExt};
+
+use super::ImportError;
+use crate::feature::{
+    export::timeclock::DEFAULT_ACCOUNT,
+    tracker::{SessionDetails, StartTime, TimeRecord},
+};
+
+const DATE_FORMAT: &str = "%Y/%m/%d";
+// seconds are optional
+const TIME_FORMATS: [&str; 2] = ["%H:%M:%S", "%H:%M"];
+
+/// Reads clock-in/out pairs; the wall-clock times are in `tz`.
+pub fn parse<R: Read>(input: R, tz: &Tz) -> Result<Vec<TimeRecord>, ImportError> {
+    let mut records = Vec::new();
+    let mut open: Option<(StartTime, SessionDetails)> = None;
+
+    for (index, line) in BufReader::new(input).lines().enumerate() {
+        let line_no = index + 1;
+        let line = line
+            .change_context(ImportError)
+            .attach_printable("unable to read timeclock file")?;
+        let line = line.trim_end();
+        let Some((code, rest)) = line.split_once(' ') else {
+            continue;
+        };
+        match code {
+            "i" | "I" => {
+                if open.is_some() {
+                    return Err(Report::new(ImportError).attach_printable(format!(
+                        "line {line_no}: clock-in while another session is open"
+                    )));
+                }
+                let (start, rest) = parse_timestamp(rest, line_no, tz)?;
+                open = Some((start.into(), details(rest)));
+            }
+            "o" | "O" => {
+                let Some((start, details)) = open.take() else {
+                    return Err(Report::new(ImportError).attach_printable(format!(
+                        "line {line_no}: clock-out without a clock-in"
+                    )));
+                };
+                let (end, _) = parse_timestamp(rest, line_no, tz)?;
+                records.push(TimeRecord::new(start, end.into(), details));
+            }
+            // comments, directives and journal entries are not ours to read
+            _ => continue,
+        }
+    }
+    if let Some((start, _)) = open {
+        tracing::warn!(
+            ?start,
+            "skipping timeclock session that was never clocked out"
+        );
+    }
+    Ok(records)
+}
+
+/// Splits `2026/10/18 09:00[:00] rest` into the timestamp and the rest of the line.
+fn parse_timestamp<'a>(
+    rest: &'a str,
+    line_no: usize,
+    tz: &Tz,
+) -> Result<(DateTime<Utc>, &'a str), ImportError> {
+    let (date, rest) = next_word(rest);
+    let (time, rest) = next_word(rest);
+    let invalid = || format!("line {line_no}: invalid timestamp '{date} {time}'");
+    let date = NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .change_context(ImportError)
+        .attach_printable_lazy(invalid)?;
+    let time = TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(time, format).ok())
+        .ok_or(ImportError)
+        .attach_printable_lazy(invalid)?;
+    let naive = date.and_time(time);
+    let local = tz
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or(ImportError)
+        .attach_printable_lazy(|| format!("line {line_no}: {naive} does not exist in {tz}"))?;
+    Ok((local.with_timezone(&Utc), rest))
+}
+
+fn next_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    text.split_at(text.find(char::is_whitespace).unwrap_or(text.len()))
+}
+
+/// Account and description are separated by two spaces; accounts may contain one.
+fn details(rest: &str) -> SessionDetails {
+    let rest = rest.trim();
+    let (account, description) = match rest.split_once("  ") {
+        Some((account, description)) => (account.trim(), Some(description.trim())),
+        None => (rest, None),
+    };
+    let project = match account {
+        "" | DEFAULT_ACCOUNT => None,
+        account => Some(account.to_string()),
+    };
+    SessionDetails {
+        project,
+        tags: Vec::new(),
+        note: description
+            .filter(|text| !text.is_empty())
+            .map(str::to_string),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::export::{timeclock::TimeclockExporter, Exporter};
+
+    #[test]
+    fn reads_sessions_with_account_and_description() {
+        let file = "\
+; hours for october
+i 2026/10/18 09:00:00 client:acme  reviewing PR 42
+o 2026/10/18 10:30:00
+
+i 2026/10/18 11:00:00 internal
+o 2026/10/18 11:15:00
+";
+
+        let records = parse(file.as_bytes(), &chrono_tz::UTC).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].project.as_deref(), Some("client:acme"));
+        assert_eq!(records[0].note.as_deref(), Some("reviewing PR 42"));
+        assert_eq!(records[0].duration().as_secs(), 90 * 60);
+        assert_eq!(records[1].project.as_deref(), Some("internal"));
+        assert_eq!(records[1].note, None);
+    }
+
+    #[test]
+    fn rejects_clock_out_without_clock_in() {
+        assert!(parse("o 2026/10/18 10:30:00\n".as_bytes(), &chrono_tz::UTC).is_err());
+    }
+
+    #[test]
+    fn accepts_times_without_seconds_in_the_given_timezone() {
+        let file = "i 2026/10/18 09:00 acme\no 2026/10/18 09:45\n";
+
+        let records = parse(file.as_bytes(), &chrono_tz::Europe::Berlin).unwrap();
+
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 7, 0, 0).unwrap();
+        assert_eq!(records[0].start.datetime(), start);
+        assert_eq!(records[0].project.as_deref(), Some("acme"));
+        assert_eq!(records[0].duration().as_secs(), 45 * 60);
+    }
+
+    #[test]
+    fn round_trips_exported_records() {
+        let file = "i 2026/10/18 09:00:00 acme  planning\no 2026/10/18 10:00:00\n";
+        let tz = chrono_tz::America::New_York;
+        let records = parse(file.as_bytes(), &tz).unwrap();
+
+        let mut out = Vec::new();
+        TimeclockExporter { tz }.export(&records, &mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), file);
+    }
+}
@@ -0,0 +1,78 @@
+use std::io::Read;
+
+use chrono::NaiveDateTime;
+use error_stack::{Result, ResultExt};
+use serde::Deserialize;
+
+use super::ImportError;
+use crate::feature::tracker::{SessionDetails, TimeRecord};
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug, Deserialize)]
+struct Interval {
+    start: String,
+    end: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    annotation: Option<String>,
+}
+
+pub fn parse<R: Read>(input: R) -> Result<Vec<TimeRecord>, ImportError> {
+    let intervals: Vec<Interval> = serde_json::from_reader(input)
+        .change_context(ImportError)
+        .attach_printable("unable to deserialize timewarrior export")?;
+
+    let mut records = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        let Some(end) = interval.end else {
+            tracing::warn!(start = interval.start, "skipping open timewarrior interval");
+            continue;
+        };
+        let mut tags = interval.tags.into_iter();
+        let details = SessionDetails {
+            project: tags.next(),
+            tags: tags.collect(),
+            note: interval.annotation,
+            ..Default::default()
+        };
+        let start = parse_timestamp(&interval.start)?;
+        let end = parse_timestamp(&end)?;
+        records.push(TimeRecord::new(start.into(), end.into(), details));
+    }
+    Ok(records)
+}
+
+fn parse_timestamp(text: &str) -> Result<chrono::DateTime<chrono::Utc>, ImportError> {
+    let naive = NaiveDateTime::parse_from_str(text, TIMESTAMP_FORMAT)
+        .change_context(ImportError)
+        .attach_printable_lazy(|| format!("invalid timewarrior timestamp '{text}'"))?;
+    Ok(naive.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn first_tag_becomes_project() {
+        let export = r#"[
+            {"id":2,"start":"20261018T090000Z","end":"20261018T103000Z","tags":["acme","review"],"annotation":"PR 42"},
+            {"id":1,"start":"20261018T110000Z"}
+        ]"#;
+
+        let records = parse(export.as_bytes()).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(
+            record.start.datetime(),
+            Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap()
+        );
+        assert_eq!(record.project.as_deref(), Some("acme"));
+        assert_eq!(record.tags, vec!["review".to_string()]);
+        assert_eq!(record.note.as_deref(), Some("PR 42"));
+    }
+}
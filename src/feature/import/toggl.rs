@@ -0,0 +1,107 @@
+use std::io::Read;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use error_stack::{Result, ResultExt};
+use serde::Deserialize;
+
+use super::ImportError;
+use crate::feature::tracker::{SessionDetails, TimeRecord};
+
+#[derive(Debug, Deserialize)]
+struct Entry {
+    #[serde(rename = "Project", default)]
+    project: Option<String>,
+    #[serde(rename = "Description", default)]
+    description: Option<String>,
+    #[serde(rename = "Start date")]
+    start_date: NaiveDate,
+    #[serde(rename = "Start time")]
+    start_time: NaiveTime,
+    #[serde(rename = "End date")]
+    end_date: NaiveDate,
+    #[serde(rename = "End time")]
+    end_time: NaiveTime,
+    #[serde(rename = "Tags", default)]
+    tags: Option<String>,
+    #[serde(rename = "Billable", default)]
+    billable: Option<String>,
+}
+
+/// Reads a detailed report; its times are taken as wall-clock times in `tz`.
+pub fn parse<R: Read>(input: R, tz: &Tz) -> Result<Vec<TimeRecord>, ImportError> {
+    let mut records = Vec::new();
+    for entry in csv::Reader::from_reader(input).deserialize::<Entry>() {
+        let entry = entry
+            .change_context(ImportError)
+            .attach_printable("unable to read toggl csv row")?;
+        let start = local_to_utc(entry.start_date, entry.start_time, tz)?;
+        let end = local_to_utc(entry.end_date, entry.end_time, tz)?;
+        let details = SessionDetails {
+            project: entry.project,
+            tags: entry
+                .tags
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect(),
+            note: entry.description,
+            non_billable: entry.billable.as_deref() == Some("No"),
+            task: None,
+        };
+        records.push(TimeRecord::new(start.into(), end.into(), details));
+    }
+    Ok(records)
+}
+
+// Toggl writes times in the exporting user's timezone without an offset
+fn local_to_utc(date: NaiveDate, time: NaiveTime, tz: &Tz) -> Result<DateTime<Utc>, ImportError> {
+    let local = tz
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .ok_or(ImportError)
+        .attach_printable_lazy(|| format!("{date} {time} does not exist in {tz}"))?;
+    Ok(local.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_detailed_report_rows() {
+        let export = "\
+User,Email,Client,Project,Task,Description,Billable,Start date,Start time,End date,End time,Duration,Tags,Amount ()
+Sam,sam@example.com,Acme Inc,acme,,Reviewing PR 42,Yes,2026-10-18,09:00:00,2026-10-18,10:30:00,01:30:00,\"review, billable\",
+Sam,sam@example.com,,,,,No,2026-10-18,11:00:00,2026-10-18,11:15:00,00:15:00,,
+";
+
+        let records = parse(export.as_bytes(), &Tz::UTC).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].project.as_deref(), Some("acme"));
+        assert_eq!(records[0].note.as_deref(), Some("Reviewing PR 42"));
+        assert_eq!(records[0].tags, vec!["review", "billable"]);
+        assert_eq!(records[0].duration().as_secs(), 90 * 60);
+        assert_eq!(records[1].project, None);
+        assert!(records[1].tags.is_empty());
+        assert!(records[0].is_billable());
+        assert!(!records[1].is_billable());
+    }
+
+    #[test]
+    fn times_are_in_the_given_timezone() {
+        let export = "\
+Project,Description,Billable,Start date,Start time,End date,End time,Tags
+acme,,Yes,2026-10-18,09:00:00,2026-10-18,10:30:00,
+";
+
+        let records = parse(export.as_bytes(), &chrono_tz::Europe::Berlin).unwrap();
+
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 7, 0, 0).unwrap();
+        assert_eq!(records[0].start.datetime(), start);
+        assert_eq!(records[0].duration().as_secs(), 90 * 60);
+    }
+}
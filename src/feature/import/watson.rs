@@ -0,0 +1,62 @@
+use std::io::Read;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use error_stack::{Result, ResultExt};
+use serde::Deserialize;
+
+use super::ImportError;
+use crate::feature::tracker::{SessionDetails, TimeRecord};
+
+#[derive(Debug, Deserialize)]
+struct Frame {
+    start: DateTime<FixedOffset>,
+    stop: DateTime<FixedOffset>,
+    project: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+pub fn parse<R: Read>(input: R) -> Result<Vec<TimeRecord>, ImportError> {
+    let frames: Vec<Frame> = serde_json::from_reader(input)
+        .change_context(ImportError)
+        .attach_printable("unable to deserialize watson log")?;
+
+    let records = frames
+        .into_iter()
+        .map(|frame| {
+            let details = SessionDetails {
+                project: Some(frame.project),
+                tags: frame.tags,
+                ..Default::default()
+            };
+            let start = frame.start.with_timezone(&Utc);
+            let end = frame.stop.with_timezone(&Utc);
+            TimeRecord::new(start.into(), end.into(), details)
+        })
+        .collect();
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn converts_offsets_to_utc() {
+        let log = r#"[
+            {"id":"a1","project":"acme","start":"2026-10-18T09:00:00+02:00","stop":"2026-10-18T10:30:00+02:00","tags":["review"]}
+        ]"#;
+
+        let records = parse(log.as_bytes()).unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].start.datetime(),
+            Utc.with_ymd_and_hms(2026, 10, 18, 7, 0, 0).unwrap()
+        );
+        assert_eq!(records[0].project.as_deref(), Some("acme"));
+        assert_eq!(records[0].tags, vec!["review".to_string()]);
+    }
+}
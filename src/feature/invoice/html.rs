@@ -0,0 +1,102 @@
+use super::{Invoice, Percent};
+
+const STYLE: &str = "body{font-family:sans-serif;max-width:48em;margin:2em auto}\
+table{border-collapse:collapse;width:100%}\
+th,td{padding:.3em .6em;border-bottom:1px solid #ccc}\
+td.num,th.num{text-align:right}\
+tr.total td{font-weight:bold}";
+
+pub fn render(invoice: &Invoice) -> String {
+    let number = escape(invoice.number_label());
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Invoice {number}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>Invoice {number}</h1>\n"
+    );
+    if !invoice.issuer.is_empty() {
+        let lines: Vec<_> = invoice.issuer.iter().map(|line| escape(line)).collect();
+        html.push_str(&format!("<p>{}</p>\n", lines.join("<br>")));
+    }
+    html.push_str(&format!(
+        "<p><strong>Bill to:</strong> {}<br><strong>Date:</strong> {}<br>\
+         <strong>Period:</strong> {} to {}</p>\n",
+        escape(&invoice.client),
+        invoice.date,
+        invoice.first_day,
+        invoice.last_day
+    ));
+
+    html.push_str(
+        "<table>\n<tr><th>Item</th><th class=\"num\">Hours</th>\
+         <th class=\"num\">Rate</th><th class=\"num\">Amount</th></tr>\n",
+    );
+    for item in &invoice.items {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{:.2}</td><td class=\"num\">{}</td>\
+             <td class=\"num\">{}</td></tr>\n",
+            escape(&item.description),
+            item.hours(),
+            item.rate,
+            escape(&invoice.money(item.amount()))
+        ));
+    }
+    let mut summary = vec![("Subtotal".to_string(), invoice.subtotal(), "")];
+    if invoice.tax_percent != Percent::default() {
+        summary.push((format!("Tax {}%", invoice.tax_percent), invoice.tax(), ""));
+    }
+    summary.push(("Total".to_string(), invoice.total(), " class=\"total\""));
+    for (label, amount, class) in summary {
+        html.push_str(&format!(
+            "<tr{class}><td colspan=\"3\">{label}</td><td class=\"num\">{}</td></tr>\n",
+            escape(&invoice.money(amount))
+        ));
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::feature::{invoice::LineItem, tracker::billing::Money};
+
+    #[test]
+    fn escapes_text_and_lists_totals() {
+        let day = |month, day| NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+        let invoice = Invoice {
+            number: None,
+            date: day(10, 1),
+            client: "Smith & <Sons>".to_string(),
+            first_day: day(9, 1),
+            last_day: day(9, 30),
+            currency: None,
+            items: vec![LineItem {
+                description: "web".to_string(),
+                worked: Duration::from_secs(2 * 3600),
+                rate: Money(5000),
+            }],
+            tax_percent: Percent::default(),
+            issuer: Vec::new(),
+            records: Vec::new(),
+        };
+
+        let html = render(&invoice);
+
+        assert!(html.contains("<h1>Invoice DRAFT</h1>"));
+        assert!(html.contains("Smith &amp; &lt;Sons&gt;"));
+        assert!(html.contains("<td class=\"num\">100.00</td></tr>"));
+        assert!(html.contains("<tr class=\"total\"><td colspan=\"3\">Total</td>"));
+        assert!(!html.contains("Tax"));
+    }
+}
@@ -0,0 +1,84 @@
+use super::{Invoice, Percent};
+
+pub fn render(invoice: &Invoice) -> String {
+    let mut text = format!("# Invoice {}\n\n", invoice.number_label());
+    if !invoice.issuer.is_empty() {
+        // two trailing spaces keep the address on separate lines
+        text.push_str(&invoice.issuer.join("  \n"));
+        text.push_str("\n\n");
+    }
+    text.push_str(&format!(
+        "**Bill to:** {}  \n**Date:** {}  \n**Period:** {} to {}\n\n",
+        invoice.client, invoice.date, invoice.first_day, invoice.last_day
+    ));
+
+    text.push_str("| Item | Hours | Rate | Amount |\n|---|---:|---:|---:|\n");
+    for item in &invoice.items {
+        text.push_str(&format!(
+            "| {} | {:.2} | {} | {} |\n",
+            item.description.replace('|', "\\|"),
+            item.hours(),
+            item.rate,
+            invoice.money(item.amount())
+        ));
+    }
+    text.push_str(&format!(
+        "| Subtotal | | | {} |\n",
+        invoice.money(invoice.subtotal())
+    ));
+    if invoice.tax_percent != Percent::default() {
+        text.push_str(&format!(
+            "| Tax {}% | | | {} |\n",
+            invoice.tax_percent,
+            invoice.money(invoice.tax())
+        ));
+    }
+    text.push_str(&format!(
+        "| **Total** | | | **{}** |\n",
+        invoice.money(invoice.total())
+    ));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::feature::{invoice::LineItem, tracker::billing::Money};
+
+    #[test]
+    fn renders_items_and_totals_as_tables() {
+        let day = |month, day| NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+        let invoice = Invoice {
+            number: Some("INV-0003".to_string()),
+            date: day(10, 1),
+            client: "acme".to_string(),
+            first_day: day(9, 1),
+            last_day: day(9, 30),
+            currency: Some("EUR".to_string()),
+            items: vec![LineItem {
+                description: "web".to_string(),
+                worked: Duration::from_secs(90 * 60),
+                rate: Money(10000),
+            }],
+            tax_percent: Percent(1900),
+            issuer: vec!["Sam Doe".to_string(), "Main St 1".to_string()],
+            records: Vec::new(),
+        };
+
+        assert_eq!(
+            render(&invoice),
+            "# Invoice INV-0003\n\n\
+             Sam Doe  \nMain St 1\n\n\
+             **Bill to:** acme  \n**Date:** 2026-10-01  \n**Period:** 2026-09-01 to 2026-09-30\n\n\
+             | Item | Hours | Rate | Amount |\n|---|---:|---:|---:|\n\
+             | web | 1.50 | 100.00 | 150.00 EUR |\n\
+             | Subtotal | | | 150.00 EUR |\n\
+             | Tax 19% | | | 28.50 EUR |\n\
+             | **Total** | | | **178.50 EUR** |\n"
+        );
+    }
+}
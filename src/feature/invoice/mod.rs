@@ -0,0 +1,402 @@
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+use chrono::{NaiveDate, TimeZone};
+use error_stack::{Report, Result};
+use serde::Deserialize;
+
+use super::tracker::{
+    billing::{div_round, hundredths, Money, RateTable},
+    reporter::GroupBy,
+    RecordId, TimeRecord,
+};
+use crate::common::deserialize_duration;
+
+mod html;
+mod markdown;
+
+#[derive(Debug, thiserror::Error)]
+#[error("invoice error")]
+pub struct InvoiceError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum InvoiceFormat {
+    #[default]
+    Markdown,
+    /// standalone HTML page, e.g. for printing to PDF
+    Html,
+}
+
+/// The `invoice` section of the config.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InvoiceSettings {
+    /// put in front of the sequence number, e.g. `INV-` gives `INV-0001`
+    pub prefix: String,
+    pub tax_percent: Percent,
+    /// quantities are rounded to a multiple of this, e.g. `15m`
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub round_to: Duration,
+    /// name and address lines printed at the top
+    pub issuer: Vec<String>,
+}
+
+impl Default for InvoiceSettings {
+    fn default() -> Self {
+        Self {
+            prefix: "INV-".to_string(),
+            tax_percent: Percent::default(),
+            round_to: Duration::from_secs(60),
+            issuer: Vec::new(),
+        }
+    }
+}
+
+/// A percentage in hundredths of a percent, read from the config as e.g. `19` or `7.5`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "f64")]
+pub struct Percent(pub i64);
+
+impl Percent {
+    /// `self` of `amount`, rounded half away from zero to the minor unit.
+    pub fn of(self, amount: Money) -> Money {
+        Money(div_round(i128::from(amount.0) * i128::from(self.0), 10_000))
+    }
+}
+
+impl TryFrom<f64> for Percent {
+    type Error = String;
+
+    fn try_from(value: f64) -> std::result::Result<Self, Self::Error> {
+        hundredths(value).map(Percent)
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let (whole, fraction) = (self.0.unsigned_abs() / 100, self.0.unsigned_abs() % 100);
+        match fraction {
+            0 => write!(f, "{sign}{whole}"),
+            _ if fraction % 10 == 0 => write!(f, "{sign}{whole}.{}", fraction / 10),
+            _ => write!(f, "{sign}{whole}.{fraction:02}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineItem {
+    pub description: String,
+    /// time billed, already rounded
+    pub worked: Duration,
+    pub rate: Money,
+}
+
+impl LineItem {
+    pub fn hours(&self) -> f64 {
+        self.worked.as_secs_f64() / 3600.0
+    }
+
+    /// Rounded to the minor unit on its own, so the lines add up to the subtotal.
+    pub fn amount(&self) -> Money {
+        self.rate.for_time(self.worked)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invoice {
+    /// `None` until the invoice is issued
+    pub number: Option<String>,
+    pub date: NaiveDate,
+    pub client: String,
+    pub first_day: NaiveDate,
+    pub last_day: NaiveDate,
+    pub currency: Option<String>,
+    pub items: Vec<LineItem>,
+    pub tax_percent: Percent,
+    pub issuer: Vec<String>,
+    /// the records billed by this invoice
+    pub records: Vec<RecordId>,
+}
+
+impl Invoice {
+    pub fn subtotal(&self) -> Money {
+        self.items.iter().map(LineItem::amount).sum()
+    }
+
+    pub fn tax(&self) -> Money {
+        self.tax_percent.of(self.subtotal())
+    }
+
+    pub fn total(&self) -> Money {
+        self.subtotal() + self.tax()
+    }
+
+    pub fn render(&self, format: InvoiceFormat) -> String {
+        match format {
+            InvoiceFormat::Markdown => markdown::render(self),
+            InvoiceFormat::Html => html::render(self),
+        }
+    }
+
+    fn number_label(&self) -> &str {
+        self.number.as_deref().unwrap_or("DRAFT")
+    }
+
+    fn money(&self, amount: Money) -> String {
+        match &self.currency {
+            Some(currency) => format!("{amount} {currency}"),
+            None => amount.to_string(),
+        }
+    }
+}
+
+/// The invoice number printed for sequence number `number`.
+pub fn invoice_number(settings: &InvoiceSettings, number: u32) -> String {
+    format!("{}{number:04}", settings.prefix)
+}
+
+/// What is billed to `client` for the days `first_day` through `last_day`.
+#[derive(Debug, Clone)]
+pub struct InvoiceRequest<'a> {
+    pub client: &'a str,
+    pub first_day: NaiveDate,
+    pub last_day: NaiveDate,
+    pub date: NaiveDate,
+    pub group_by: GroupBy,
+}
+
+/// Builds an unnumbered invoice from the billable, not yet invoiced records of the client.
+///
+/// Records are expected to be limited to the invoiced days already; one line item is made
+/// per project or day and rate.
+pub fn build<Tz: TimeZone>(
+    records: &[TimeRecord],
+    request: &InvoiceRequest,
+    rates: &RateTable,
+    settings: &InvoiceSettings,
+    tz: &Tz,
+) -> Result<Invoice, InvoiceError> {
+    let mut currency = None;
+    let mut ids = Vec::new();
+    let mut groups: BTreeMap<(String, Money), Duration> = BTreeMap::new();
+    for record in records {
+        let rate = rates.rate_for(record);
+        if rate.client.as_deref() != Some(request.client)
+            || !record.is_billable()
+            || record.invoice.is_some()
+        {
+            continue;
+        }
+        let Some(hourly) = rate.hourly else {
+            let project = record.project.as_deref().unwrap_or("-");
+            return Err(Report::new(InvoiceError)
+                .attach_printable(format!("no rate for project {project}")));
+        };
+        let description = match request.group_by {
+            GroupBy::Project => record.project.clone().unwrap_or_default(),
+            GroupBy::Day => record
+                .start
+                .datetime()
+                .with_timezone(tz)
+                .date_naive()
+                .to_string(),
+        };
+        *groups.entry((description, hourly)).or_default() += record.duration();
+        currency = rate.currency;
+        ids.push(record.id);
+    }
+    if ids.is_empty() {
+        return Err(Report::new(InvoiceError).attach_printable(format!(
+            "nothing to invoice for {} from {} to {}",
+            request.client, request.first_day, request.last_day
+        )));
+    }
+
+    let items = groups
+        .into_iter()
+        .map(|((description, rate), worked)| LineItem {
+            description,
+            worked: round(worked, settings.round_to),
+            rate,
+        })
+        .collect();
+    Ok(Invoice {
+        number: None,
+        date: request.date,
+        client: request.client.to_string(),
+        first_day: request.first_day,
+        last_day: request.last_day,
+        currency,
+        items,
+        tax_percent: settings.tax_percent,
+        issuer: settings.issuer.clone(),
+        records: ids,
+    })
+}
+
+/// Rounds to the nearest multiple of `step`.
+fn round(duration: Duration, step: Duration) -> Duration {
+    let step = step.as_secs().max(1);
+    let steps = (duration.as_secs() + step / 2) / step;
+    Duration::from_secs(steps * step)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::feature::tracker::SessionDetails;
+
+    fn record(project: &str, day: u32, minutes: i64, tags: &[&str]) -> TimeRecord {
+        let start = Utc.with_ymd_and_hms(2026, 9, day, 9, 0, 0).unwrap();
+        let details = SessionDetails {
+            project: Some(project.to_string()),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        };
+        let end = start + chrono::Duration::minutes(minutes);
+        TimeRecord::new(start.into(), end.into(), details)
+    }
+
+    fn rates() -> RateTable {
+        serde_json::from_str(
+            r#"{"clients":{
+                "acme":{"rate":100,"currency":"EUR","projects":["web","api"]},
+                "globex":{"rate":80,"projects":["intranet"]}
+            },"tags":{"urgent":150}}"#,
+        )
+        .unwrap()
+    }
+
+    fn request(group_by: GroupBy) -> InvoiceRequest<'static> {
+        InvoiceRequest {
+            client: "acme",
+            first_day: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            last_day: NaiveDate::from_ymd_opt(2026, 9, 30).unwrap(),
+            date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            group_by,
+        }
+    }
+
+    fn settings() -> InvoiceSettings {
+        InvoiceSettings {
+            tax_percent: Percent(1900),
+            round_to: Duration::from_secs(15 * 60),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_billable_records_of_the_client_by_project_and_rate() {
+        let mut non_billable = record("web", 2, 60, &[]);
+        non_billable.non_billable = true;
+        let mut invoiced = record("web", 3, 60, &[]);
+        invoiced.invoice = Some(1);
+        let records = [
+            record("web", 1, 50, &[]),
+            record("web", 2, 45, &[]),
+            record("api", 2, 60, &["urgent"]),
+            record("intranet", 2, 60, &[]),
+            non_billable,
+            invoiced,
+        ];
+
+        let invoice = build(
+            &records,
+            &request(GroupBy::Project),
+            &rates(),
+            &settings(),
+            &Utc,
+        )
+        .unwrap();
+
+        assert_eq!(invoice.records.len(), 3);
+        assert_eq!(
+            invoice.items,
+            vec![
+                LineItem {
+                    description: "api".to_string(),
+                    worked: Duration::from_secs(3600),
+                    rate: Money(15000)
+                },
+                // 95 minutes rounded to the nearest quarter hour
+                LineItem {
+                    description: "web".to_string(),
+                    worked: Duration::from_secs(90 * 60),
+                    rate: Money(10000)
+                },
+            ]
+        );
+        assert_eq!(invoice.subtotal(), Money(30000));
+        assert_eq!(invoice.tax(), Money(5700));
+        assert_eq!(invoice.total(), Money(35700));
+        assert_eq!(invoice.currency.as_deref(), Some("EUR"));
+    }
+
+    #[test]
+    fn groups_by_day() {
+        let records = [record("web", 1, 60, &[]), record("api", 1, 60, &[])];
+
+        let invoice = build(
+            &records,
+            &request(GroupBy::Day),
+            &rates(),
+            &settings(),
+            &Utc,
+        )
+        .unwrap();
+
+        assert_eq!(invoice.items.len(), 1);
+        assert_eq!(invoice.items[0].description, "2026-09-01");
+        assert_eq!(invoice.items[0].hours(), 2.0);
+    }
+
+    #[test]
+    fn rounds_each_line_before_adding_them_up() {
+        let rates: RateTable =
+            serde_json::from_str(r#"{"clients":{"acme":{"rate":99.95,"projects":["web","api"]}}}"#)
+                .unwrap();
+        let settings = InvoiceSettings {
+            tax_percent: Percent(750),
+            ..Default::default()
+        };
+        let records = [record("web", 1, 20, &[]), record("api", 1, 20, &[])];
+
+        let invoice = build(
+            &records,
+            &request(GroupBy::Project),
+            &rates,
+            &settings,
+            &Utc,
+        )
+        .unwrap();
+
+        // a third of an hour at 99.95 is 33.3166…, rounded to 33.32 per line
+        assert_eq!(invoice.items[0].amount(), Money(3332));
+        assert_eq!(invoice.subtotal(), Money(6664));
+        // 7.5% of 66.64 is 4.998
+        assert_eq!(invoice.tax(), Money(500));
+        assert_eq!(invoice.total(), Money(7164));
+        assert_eq!(invoice.tax_percent.to_string(), "7.5");
+    }
+
+    #[test]
+    fn nothing_to_bill_is_an_error() {
+        let records = [record("intranet", 1, 60, &[])];
+
+        assert!(build(
+            &records,
+            &request(GroupBy::Project),
+            &rates(),
+            &settings(),
+            &Utc
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn numbers_use_the_configured_prefix() {
+        assert_eq!(invoice_number(&settings(), 7), "INV-0007");
+    }
+}
@@ -0,0 +1,177 @@
+use std::{collections::BTreeMap, fmt::Write, fs, path::Path, time::Duration};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use error_stack::{Result, ResultExt};
+
+use super::tracker::{
+    reporter::{Period, ReportTimespan, Reporter},
+    ActiveSession,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("metrics error")]
+pub struct MetricsError;
+
+/// Content type of [`render`]'s output.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Gauges in the Prometheus text format: running state and session length per timer, and
+/// the time tracked today and this week per project, running sessions included.
+///
+/// `timer` always gets a running gauge, so dashboards see it drop to 0 when it stops.
+pub fn render<T: Reporter>(
+    tracker: &T,
+    timer: &str,
+    now: DateTime<Utc>,
+    tz: &Tz,
+) -> Result<String, MetricsError> {
+    let sessions = tracker
+        .active_sessions()
+        .change_context(MetricsError)
+        .attach_printable("failed to read running timers")?;
+    let today = now.with_timezone(tz).date_naive();
+    let mut text = String::new();
+
+    gauge(
+        &mut text,
+        "track_running",
+        "Whether the timer is running and not paused.",
+    );
+    if !sessions.iter().any(|session| session.timer == timer) {
+        sample(&mut text, "track_running", &[("timer", timer)], 0);
+    }
+    for session in &sessions {
+        let running = u64::from(!session.is_paused());
+        sample(
+            &mut text,
+            "track_running",
+            &[("timer", &session.timer)],
+            running,
+        );
+    }
+    gauge(
+        &mut text,
+        "track_session_seconds",
+        "Time worked in the running session, without breaks.",
+    );
+    for session in &sessions {
+        let project = session.details.project.as_deref().unwrap_or_default();
+        sample(
+            &mut text,
+            "track_session_seconds",
+            &[("timer", &session.timer), ("project", project)],
+            session.elapsed(now).as_secs(),
+        );
+    }
+
+    for (name, help, period) in [
+        (
+            "track_today_seconds",
+            "Time tracked today per project.",
+            Period::Day,
+        ),
+        (
+            "track_week_seconds",
+            "Time tracked this week per project.",
+            Period::Week,
+        ),
+    ] {
+        let (from, to) = period.bounds(today, tz);
+        let timespan = ReportTimespan::Range { from, to };
+        let mut totals = tracker
+            .project_totals(timespan)
+            .change_context(MetricsError)
+            .attach_printable("failed to sum tracked time")?;
+        add_running(&mut totals, &sessions, timespan, now);
+        gauge(&mut text, name, help);
+        for (project, total) in totals {
+            let project = project.as_deref().unwrap_or_default();
+            sample(&mut text, name, &[("project", project)], total.as_secs());
+        }
+    }
+    Ok(text)
+}
+
+/// Replaces `path` with `text` through a temporary file in the same directory, so the
+/// node exporter's textfile collector never reads a half-written file.
+pub fn write_textfile(path: &Path, text: &str) -> Result<(), MetricsError> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = path.with_file_name(format!(".{name}.tmp"));
+    fs::write(&temp, text)
+        .change_context(MetricsError)
+        .attach_printable_lazy(|| format!("failed to write {}", temp.display()))?;
+    fs::rename(&temp, path)
+        .change_context(MetricsError)
+        .attach_printable_lazy(|| format!("failed to replace {}", path.display()))
+}
+
+fn add_running(
+    totals: &mut BTreeMap<Option<String>, Duration>,
+    sessions: &[ActiveSession],
+    timespan: ReportTimespan,
+    now: DateTime<Utc>,
+) {
+    for session in sessions.iter().filter(|s| timespan.contains(&s.start)) {
+        *totals.entry(session.details.project.clone()).or_default() += session.elapsed(now);
+    }
+}
+
+fn gauge(text: &mut String, name: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {name} {help}\n# TYPE {name} gauge");
+}
+
+fn sample(text: &mut String, name: &str, labels: &[(&str, &str)], value: u64) {
+    let labels: Vec<_> = labels
+        .iter()
+        .map(|(label, value)| format!("{label}=\"{}\"", escape(value)))
+        .collect();
+    let _ = writeln!(text, "{name}{{{}}} {value}", labels.join(","));
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::feature::tracker::{flatfile::FlatFileTracker, SessionDetails, TimeRecord, Tracker};
+
+    #[test]
+    fn renders_running_state_and_totals_per_project() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker =
+            FlatFileTracker::new(dir.path().join("db.json"), dir.path().join("lock.json"));
+        let record = |day: u32, project: &str| {
+            let start = Utc.with_ymd_and_hms(2026, 10, day, 9, 0, 0).unwrap();
+            let end = start + chrono::Duration::hours(2);
+            let details = SessionDetails {
+                project: Some(project.to_string()),
+                ..Default::default()
+            };
+            TimeRecord::new(start.into(), end.into(), details)
+        };
+        tracker
+            .add_records(vec![
+                record(19, "web"),
+                record(20, "web"),
+                record(20, "a\"b"),
+            ])
+            .unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 10, 20, 12, 0, 0).unwrap();
+
+        let text = render(&tracker, "default", now, &chrono_tz::UTC).unwrap();
+
+        assert!(text.contains("# TYPE track_running gauge\ntrack_running{timer=\"default\"} 0\n"));
+        assert!(text.contains("track_today_seconds{project=\"a\\\"b\"} 7200\n"));
+        assert!(text.contains("track_today_seconds{project=\"web\"} 7200\n"));
+        assert!(text.contains("track_week_seconds{project=\"web\"} 14400\n"));
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    io::{self, Write},
+    process,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use error_stack::{Report, Result, ResultExt};
+use serde::Deserialize;
+
+use super::tracker::{SessionDetails, StartupStatus, TimeRecord, Tracker};
+use crate::error::Suggestion;
+
+/// Tag of every work interval recorded by `track pomodoro`.
+pub const TAG: &str = "pomodoro";
+/// Extra tag of a work interval that was cut short.
+pub const INTERRUPTED_TAG: &str = "interrupted";
+
+/// How often the timer checks for an interruption.
+const TICK: Duration = Duration::from_millis(200);
+
+#[derive(Debug, thiserror::Error)]
+#[error("pomodoro error")]
+pub struct PomodoroError;
+
+/// The `pomodoro` section of the config.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PomodoroSettings {
+    /// shell command run at every transition instead of ringing the bell; it gets
+    /// `TRACK_POMODORO_PHASE` (`work`, `break` or `done`) and `TRACK_POMODORO_CYCLE`
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Work,
+    Break,
+}
+
+impl Phase {
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Work => "work",
+            Phase::Break => "break",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plan {
+    pub work: Duration,
+    pub rest: Duration,
+    pub cycles: u32,
+}
+
+impl Plan {
+    /// Work and break intervals with their cycle number; there is no break after the last cycle.
+    pub fn phases(&self) -> Vec<(u32, Phase, Duration)> {
+        let mut phases = Vec::new();
+        for cycle in 1..=self.cycles {
+            phases.push((cycle, Phase::Work, self.work));
+            if cycle < self.cycles && !self.rest.is_zero() {
+                phases.push((cycle, Phase::Break, self.rest));
+            }
+        }
+        phases
+    }
+}
+
+/// Number of finished pomodoros among `records`.
+pub fn completed(records: &[TimeRecord]) -> usize {
+    records
+        .iter()
+        .filter(|rec| rec.tags.iter().any(|tag| tag == TAG))
+        .filter(|rec| !rec.tags.iter().any(|tag| tag == INTERRUPTED_TAG))
+        .count()
+}
+
+/// Runs the plan in the foreground, tracking each work interval with `details` plus the
+/// `pomodoro` tag, until it is done or `interrupted` is set.
+///
+/// Returns the number of completed work intervals. A work interval that is interrupted is
+/// still recorded, tagged `interrupted`. Fails without touching it if `timer`, the timer
+/// the tracker operates on, is already running.
+pub fn run<T: Tracker>(
+    tracker: &mut T,
+    timer: &str,
+    plan: &Plan,
+    mut details: SessionDetails,
+    settings: &PomodoroSettings,
+    interrupted: &AtomicBool,
+    tz: &Tz,
+) -> Result<u32, PomodoroError> {
+    if !details.tags.iter().any(|tag| tag == TAG) {
+        details.tags.push(TAG.to_string());
+    }
+    let mut completed = 0;
+    for (cycle, phase, length) in plan.phases() {
+        if phase == Phase::Work {
+            let status = tracker
+                .start_with(details.clone())
+                .change_context(PomodoroError)
+                .attach_printable("failed to start the work interval")?;
+            if let StartupStatus::Running(timer) = status {
+                return Err(Report::new(PomodoroError)
+                    .attach_printable(format!("timer '{timer}' is already running"))
+                    .attach(Suggestion(
+                        "stop it first, or use --timer to run alongside it",
+                    )));
+            }
+        }
+        notify(settings, phase.name(), cycle);
+        let until = Utc::now() + chrono::Duration::from_std(length).unwrap_or_default();
+        println!(
+            "{} {cycle}/{}: {} until {}",
+            match phase {
+                Phase::Work => "Pomodoro",
+                Phase::Break => "Break",
+            },
+            plan.cycles,
+            phase.name(),
+            until.with_timezone(tz).format("%H:%M:%S")
+        );
+        let finished = wait(length, interrupted);
+        let mut started = None;
+        if phase == Phase::Work {
+            started = session_start(tracker, timer)?;
+            tracker
+                .stop()
+                .change_context(PomodoroError)
+                .attach_printable("failed to stop the work interval")?;
+        }
+        if !finished {
+            if let Some(start) = started {
+                mark_interrupted(tracker, start)?;
+            }
+            println!("Interrupted after {completed} completed pomodoros");
+            return Ok(completed);
+        }
+        if phase == Phase::Work {
+            completed += 1;
+        }
+    }
+    notify(settings, "done", plan.cycles);
+    println!("Done, {completed} pomodoros completed");
+    Ok(completed)
+}
+
+/// Sleeps for `length`; false if `interrupted` was set before that.
+fn wait(length: Duration, interrupted: &AtomicBool) -> bool {
+    let end = Instant::now() + length;
+    loop {
+        if interrupted.load(Ordering::Relaxed) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= end {
+            return true;
+        }
+        thread::sleep(TICK.min(end - now));
+    }
+}
+
+/// Start of the session running on `timer`, to find its record once it is stopped.
+fn session_start<T: Tracker>(
+    tracker: &T,
+    timer: &str,
+) -> Result<Option<DateTime<Utc>>, PomodoroError> {
+    let sessions = tracker
+        .active_sessions()
+        .change_context(PomodoroError)
+        .attach_printable("failed to read running timers")?;
+    Ok(sessions
+        .into_iter()
+        .find(|session| session.timer == timer)
+        .map(|session| session.start.datetime()))
+}
+
+fn mark_interrupted<T: Tracker>(
+    tracker: &mut T,
+    start: DateTime<Utc>,
+) -> Result<(), PomodoroError> {
+    let interrupted = tracker
+        .records()
+        .change_context(PomodoroError)
+        .attach_printable("failed to read the interrupted record")?
+        .find(|rec| rec.start.datetime() == start);
+    let Some(mut record) = interrupted else {
+        return Ok(());
+    };
+    record.tags.push(INTERRUPTED_TAG.to_string());
+    tracker
+        .update_record(record)
+        .change_context(PomodoroError)
+        .attach_printable("failed to tag the interrupted record")
+}
+
+/// Runs the configured command, or rings the terminal bell.
+fn notify(settings: &PomodoroSettings, phase: &str, cycle: u32) {
+    let Some(command) = &settings.command else {
+        print!("\x07");
+        let _ = io::stdout().flush();
+        return;
+    };
+    let status = process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("TRACK_POMODORO_PHASE", phase)
+        .env("TRACK_POMODORO_CYCLE", cycle.to_string())
+        .status();
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => tracing::warn!(%status, command, "pomodoro command failed"),
+        Err(error) => tracing::warn!(%error, command, "unable to run pomodoro command"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::feature::tracker::{flatfile::FlatFileTracker, DEFAULT_TIMER};
+
+    #[test]
+    fn breaks_separate_work_intervals() {
+        let minutes = |minutes: u64| Duration::from_secs(minutes * 60);
+        let plan = Plan {
+            work: minutes(25),
+            rest: minutes(5),
+            cycles: 3,
+        };
+
+        let phases: Vec<_> = plan
+            .phases()
+            .into_iter()
+            .map(|(cycle, phase, _)| (cycle, phase))
+            .collect();
+
+        assert_eq!(
+            phases,
+            vec![
+                (1, Phase::Work),
+                (1, Phase::Break),
+                (2, Phase::Work),
+                (2, Phase::Break),
+                (3, Phase::Work),
+            ]
+        );
+    }
+
+    #[test]
+    fn interrupted_intervals_are_not_counted() {
+        let record = |tags: &[&str]| {
+            let start = Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap();
+            let details = SessionDetails {
+                tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                ..Default::default()
+            };
+            let end = start + chrono::Duration::minutes(25);
+            TimeRecord::new(start.into(), end.into(), details)
+        };
+        let records = [
+            record(&[TAG]),
+            record(&["deep", TAG]),
+            record(&[TAG, INTERRUPTED_TAG]),
+            record(&[]),
+        ];
+
+        assert_eq!(completed(&records), 2);
+    }
+
+    #[test]
+    fn a_running_timer_is_left_alone() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker =
+            FlatFileTracker::new(dir.path().join("db.json"), dir.path().join("lock.json"));
+        tracker.start().unwrap();
+        let plan = Plan {
+            work: Duration::from_millis(10),
+            rest: Duration::ZERO,
+            cycles: 1,
+        };
+
+        let result = run(
+            &mut tracker,
+            DEFAULT_TIMER,
+            &plan,
+            SessionDetails::default(),
+            &PomodoroSettings::default(),
+            &AtomicBool::new(false),
+            &Tz::UTC,
+        );
+
+        assert!(result.is_err());
+        assert!(tracker.is_running().unwrap());
+        assert_eq!(tracker.records().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn interrupting_tags_the_interval_of_this_timer() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker =
+            FlatFileTracker::new(dir.path().join("db.json"), dir.path().join("lock.json"));
+        // a pomodoro of another timer that started later
+        let start = Utc::now() + chrono::Duration::hours(1);
+        let details = SessionDetails {
+            tags: vec![TAG.to_string()],
+            ..Default::default()
+        };
+        let other = TimeRecord::new(
+            start.into(),
+            (start + chrono::Duration::minutes(25)).into(),
+            details,
+        );
+        tracker.add_records(vec![other.clone()]).unwrap();
+        let plan = Plan {
+            work: Duration::from_secs(60),
+            rest: Duration::ZERO,
+            cycles: 1,
+        };
+
+        let completed = run(
+            &mut tracker,
+            DEFAULT_TIMER,
+            &plan,
+            SessionDetails::default(),
+            &PomodoroSettings::default(),
+            &AtomicBool::new(true),
+            &Tz::UTC,
+        )
+        .unwrap();
+
+        assert_eq!(completed, 0);
+        let records: Vec<_> = tracker.records().unwrap().collect();
+        let interrupted: Vec<_> = records
+            .iter()
+            .filter(|rec| rec.tags.iter().any(|tag| tag == INTERRUPTED_TAG))
+            .collect();
+        assert_eq!(interrupted.len(), 1);
+        assert_ne!(interrupted[0].id, other.id);
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use super::{
+    report_fmt::{CompactFormatter, DurationFormat, HMSFormatter},
+    tracker::ActiveSession,
+};
+
+/// Format used when none is given.
+pub const DEFAULT_FORMAT: &str = "{project} {elapsed}";
+
+/// Fills the placeholders of `format` from the running session.
+///
+/// Known placeholders are `{project}`, `{tags}`, `{note}`, `{task}`, `{timer}`, `{state}`
+/// (`running` or `paused`), `{elapsed}` (e.g. `1h05m`) and `{elapsed_hms}`. Missing values
+/// are left empty and the result is trimmed, so an untitled session shows just the time.
+pub fn render(format: &str, session: &ActiveSession, now: DateTime<Utc>) -> String {
+    let elapsed = session.elapsed(now);
+    let details = &session.details;
+    let values = [
+        ("project", details.project.clone().unwrap_or_default()),
+        ("tags", details.tags.join(",")),
+        ("note", details.note.clone().unwrap_or_default()),
+        ("task", details.task.clone().unwrap_or_default()),
+        ("timer", session.timer.clone()),
+        ("state", state(session).to_string()),
+        ("elapsed", CompactFormatter.format(elapsed)),
+        ("elapsed_hms", HMSFormatter.format(elapsed)),
+    ];
+    let mut text = format.to_string();
+    for (name, value) in values {
+        text = text.replace(&format!("{{{name}}}"), &value);
+    }
+    text.trim().to_string()
+}
+
+fn state(session: &ActiveSession) -> &'static str {
+    match session.is_paused() {
+        true => "paused",
+        false => "running",
+    }
+}
+
+/// A Waybar custom module block; i3status-rust and others read the same fields.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WaybarBlock {
+    pub text: String,
+    pub tooltip: String,
+    /// `running`, `paused` or `idle`, for styling
+    pub class: &'static str,
+}
+
+/// The block for the session, or an idle block with `idle` as text when none is running.
+pub fn waybar(
+    format: &str,
+    session: Option<&ActiveSession>,
+    idle: &str,
+    now: DateTime<Utc>,
+    tz: &Tz,
+) -> WaybarBlock {
+    let Some(session) = session else {
+        return WaybarBlock {
+            text: idle.to_string(),
+            tooltip: "no session is running".to_string(),
+            class: "idle",
+        };
+    };
+    let mut tooltip = format!(
+        "{} since {}",
+        session.timer,
+        session.start.datetime().with_timezone(tz).format("%H:%M")
+    );
+    if let Some(note) = &session.details.note {
+        tooltip.push_str(&format!("\n{note}"));
+    }
+    WaybarBlock {
+        text: render(format, session, now),
+        tooltip,
+        class: state(session),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::feature::tracker::SessionDetails;
+
+    fn session(project: Option<&str>) -> ActiveSession {
+        ActiveSession {
+            timer: "default".to_string(),
+            start: Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap().into(),
+            details: SessionDetails {
+                project: project.map(str::to_string),
+                tags: vec!["a".to_string(), "b".to_string()],
+                ..Default::default()
+            },
+            breaks: Vec::new(),
+            paused_at: None,
+        }
+    }
+
+    #[test]
+    fn fills_placeholders() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 10, 5, 30).unwrap();
+
+        assert_eq!(
+            render("{project} {elapsed} [{tags}]", &session(Some("web")), now),
+            "web 1h05m [a,b]"
+        );
+        assert_eq!(render(DEFAULT_FORMAT, &session(None), now), "1h05m");
+        assert_eq!(
+            render("{elapsed_hms} {state}", &session(None), now),
+            "01:05:30 running"
+        );
+    }
+
+    #[test]
+    fn waybar_blocks_have_state_classes() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 9, 30, 0).unwrap();
+        let mut paused = session(Some("web"));
+        paused.paused_at = Some(now);
+
+        let tz = chrono_tz::Europe::Berlin;
+        assert_eq!(waybar(DEFAULT_FORMAT, None, "", now, &tz).class, "idle");
+        let block = waybar(DEFAULT_FORMAT, Some(&paused), "", now, &tz);
+        assert_eq!(block.class, "paused");
+        assert_eq!(block.text, "web 30m");
+        assert_eq!(
+            serde_json::to_string(&waybar(
+                "{project}",
+                Some(&session(Some("web"))),
+                "",
+                now,
+                &tz
+            ))
+            .unwrap(),
+            r#"{"text":"web","tooltip":"default since 11:00","class":"running"}"#
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use super::{DurationFormat, HMSFormatter};
+use crate::feature::tracker::{billing::Money, reporter::BillingLine};
+
+const NO_NAME_LABEL: &str = "-";
+
+/// One row per client, project and rate, followed by the total amount per currency.
+pub fn render(lines: &[BillingLine]) -> String {
+    if lines.is_empty() {
+        return "no records in this window\n".to_string();
+    }
+    let label = |name: &Option<String>| name.clone().unwrap_or_else(|| NO_NAME_LABEL.to_string());
+    let rows: Vec<[String; 6]> = lines
+        .iter()
+        .map(|line| {
+            let currency = line.currency.as_deref().unwrap_or_default();
+            [
+                label(&line.client),
+                label(&line.project),
+                HMSFormatter.format(line.billable),
+                HMSFormatter.format(line.non_billable),
+                line.rate.map(|rate| rate.to_string()).unwrap_or_default(),
+                line.amount()
+                    .map(|amount| format!("{amount} {currency}").trim_end().to_string())
+                    .unwrap_or_else(|| "no rate".to_string()),
+            ]
+        })
+        .collect();
+    let header = [
+        "client",
+        "project",
+        "billable",
+        "non-billable",
+        "rate",
+        "amount",
+    ]
+    .map(str::to_string);
+    let widths: Vec<_> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut text = String::new();
+    for row in [&header].into_iter().chain(&rows) {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 | 1 => format!("{cell:width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect();
+        text.push_str(cells.join("  ").trim_end());
+        text.push('\n');
+    }
+
+    let mut totals: BTreeMap<&str, Money> = BTreeMap::new();
+    for line in lines {
+        if let Some(amount) = line.amount() {
+            *totals
+                .entry(line.currency.as_deref().unwrap_or_default())
+                .or_default() += amount;
+        }
+    }
+    for (currency, amount) in totals {
+        text.push_str(format!("total {amount} {currency}").trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn lists_amounts_and_totals_per_currency() {
+        let hours = |hours: u64| Duration::from_secs(hours * 3600);
+        let line =
+            |client: Option<&str>, rate: Option<Money>, currency: &str, billable| BillingLine {
+                client: client.map(str::to_string),
+                project: Some("web".to_string()),
+                rate,
+                currency: Some(currency.to_string()),
+                billable: hours(billable),
+                non_billable: hours(1),
+            };
+        let lines = [
+            line(Some("acme"), Some(Money(10000)), "EUR", 2),
+            line(Some("acme"), Some(Money(15000)), "EUR", 1),
+            line(Some("globex"), Some(Money(8000)), "USD", 3),
+            line(None, None, "EUR", 1),
+        ];
+
+        let text = render(&lines);
+
+        assert_eq!(
+            text,
+            "client  project  billable  non-billable    rate      amount\n\
+             acme    web      02:00:00      01:00:00  100.00  200.00 EUR\n\
+             acme    web      01:00:00      01:00:00  150.00  150.00 EUR\n\
+             globex  web      03:00:00      01:00:00   80.00  240.00 USD\n\
+             -       web      01:00:00      01:00:00             no rate\n\
+             total 350.00 EUR\n\
+             total 240.00 USD\n"
+        );
+    }
+}
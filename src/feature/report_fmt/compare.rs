@@ -0,0 +1,93 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use super::{format_signed, DurationFormat, HMSFormatter};
+use crate::feature::tracker::TimeRecord;
+
+const NO_PROJECT_LABEL: &str = "-";
+
+/// Per project totals of two periods side by side, with absolute and relative change.
+pub fn render(current: &[TimeRecord], previous: &[TimeRecord]) -> String {
+    let mut projects: BTreeMap<&str, (Duration, Duration)> = BTreeMap::new();
+    for record in current {
+        projects.entry(project(record)).or_default().0 += record.duration();
+    }
+    for record in previous {
+        projects.entry(project(record)).or_default().1 += record.duration();
+    }
+    let width = projects
+        .keys()
+        .map(|name| name.chars().count())
+        .chain(["project".len(), "total".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut text = format!(
+        "{:width$}  {:>9}  {:>9}  {:>9}  {:>7}\n",
+        "project", "current", "previous", "change", "%"
+    );
+    let mut totals = (Duration::ZERO, Duration::ZERO);
+    for (name, (now, before)) in &projects {
+        totals.0 += *now;
+        totals.1 += *before;
+        text.push_str(&row(name, *now, *before, width));
+    }
+    text.push_str(&row("total", totals.0, totals.1, width));
+    text
+}
+
+fn project(record: &TimeRecord) -> &str {
+    record.project.as_deref().unwrap_or(NO_PROJECT_LABEL)
+}
+
+fn row(name: &str, current: Duration, previous: Duration, width: usize) -> String {
+    let signed = |duration| chrono::Duration::from_std(duration).unwrap_or_default();
+    let percent = match previous.is_zero() {
+        true if current.is_zero() => "0%".to_string(),
+        true => "new".to_string(),
+        false => {
+            let change = current.as_secs_f64() / previous.as_secs_f64() - 1.0;
+            format!("{:+.0}%", change * 100.0)
+        }
+    };
+    format!(
+        "{name:width$}  {:>9}  {:>9}  {:>9}  {percent:>7}\n",
+        HMSFormatter.format(current),
+        HMSFormatter.format(previous),
+        format_signed(signed(current) - signed(previous)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::feature::tracker::SessionDetails;
+
+    fn record(project: Option<&str>, hours: i64) -> TimeRecord {
+        let start = Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap();
+        let details = SessionDetails {
+            project: project.map(str::to_string),
+            ..Default::default()
+        };
+        let end = start + chrono::Duration::hours(hours);
+        TimeRecord::new(start.into(), end.into(), details)
+    }
+
+    #[test]
+    fn shows_absolute_and_relative_change_per_project() {
+        let current = [record(Some("acme"), 5), record(Some("beta"), 2)];
+        let previous = [record(Some("acme"), 4), record(None, 2)];
+
+        let text = render(&current, &previous);
+
+        assert_eq!(
+            text,
+            "project    current   previous     change        %\n\
+             -         00:00:00   02:00:00  -02:00:00    -100%\n\
+             acme      05:00:00   04:00:00  +01:00:00     +25%\n\
+             beta      02:00:00   00:00:00  +02:00:00      new\n\
+             total     07:00:00   06:00:00  +01:00:00     +17%\n"
+        );
+    }
+}
@@ -1,5 +1,19 @@
+use chrono_tz::Tz;
+
+use super::tracker::TimeRecord;
+
+pub mod billing;
+pub mod compare;
+
 #[derive(Default, Debug)]
 pub struct HMSFormatter;
+
+/// Org-mode clock duration, e.g. ` 1:30`
+#[derive(Default, Debug)]
+pub struct OrgClockFormatter;
+/// Hours and minutes for tight spaces, e.g. `1h05m` or `45m`
+#[derive(Default, Debug)]
+pub struct CompactFormatter;
 pub trait DurationFormat {
     fn format(&self, duration: std::time::Duration) -> String;
 }
@@ -14,6 +28,68 @@ impl DurationFormat for HMSFormatter {
     }
 }
 
+impl DurationFormat for OrgClockFormatter {
+    fn format(&self, duration: std::time::Duration) -> String {
+        let minutes = duration.as_secs() / 60;
+        format!("{:2}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+impl DurationFormat for CompactFormatter {
+    fn format(&self, duration: std::time::Duration) -> String {
+        let minutes = duration.as_secs() / 60;
+        match minutes / 60 {
+            0 => format!("{minutes}m"),
+            hours => format!("{hours}h{:02}m", minutes % 60),
+        }
+    }
+}
+
+/// `+HH:MM:SS` or `-HH:MM:SS`, e.g. for overtime and deltas.
+pub fn format_signed(delta: chrono::Duration) -> String {
+    let sign = if delta < chrono::Duration::zero() {
+        '-'
+    } else {
+        '+'
+    };
+    let magnitude = delta.abs().to_std().unwrap_or_default();
+    format!("{sign}{}", HMSFormatter.format(magnitude))
+}
+
+/// One line per record for `log`: start and end in `tz`, duration, project, tags and note.
+pub fn log_line(record: &TimeRecord, tz: &Tz) -> String {
+    let start = record.start.datetime().with_timezone(tz);
+    let end = record.end.datetime().with_timezone(tz);
+    let end_format = if start.date_naive() == end.date_naive() {
+        "%H:%M"
+    } else {
+        "%Y-%m-%d %H:%M"
+    };
+    let mut line = format!(
+        "{} - {}  {}",
+        start.format("%Y-%m-%d %H:%M"),
+        end.format(end_format),
+        HMSFormatter.format(record.duration())
+    );
+    if !record.breaks.is_empty() {
+        let gross = HMSFormatter.format(record.gross_duration());
+        line.push_str(&format!(" (gross {gross})"));
+    }
+    if !record.is_billable() {
+        line.push_str(" non-billable");
+    }
+    if let Some(project) = &record.project {
+        line.push_str(&format!("  {project}"));
+    }
+    if !record.tags.is_empty() {
+        line.push_str(&format!("  [{}]", record.tags.join(", ")));
+    }
+    if let Some(note) = &record.note {
+        line.push_str(&format!("  {note}"));
+    }
+    line
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -27,4 +103,52 @@ mod tests {
         let text = formatter.format(duration);
         assert_eq!(text, "00:00:05");
     }
+
+    #[test]
+    fn formats_signed_durations() {
+        assert_eq!(format_signed(chrono::Duration::minutes(-90)), "-01:30:00");
+        assert_eq!(format_signed(chrono::Duration::zero()), "+00:00:00");
+    }
+
+    #[test]
+    fn log_line_shows_project_tags_and_note() {
+        use crate::feature::tracker::SessionDetails;
+        use chrono::{TimeZone, Utc};
+
+        let start = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 10, 18, 9, 45, 0).unwrap();
+        let details = SessionDetails {
+            project: Some("acme".to_string()),
+            tags: vec!["review".to_string()],
+            note: Some("reviewing PR 42".to_string()),
+            ..Default::default()
+        };
+        let record = TimeRecord::new(start.into(), end.into(), details);
+
+        let line = log_line(&record, &Tz::UTC);
+
+        assert_eq!(
+            line,
+            "2026-10-18 09:00 - 09:45  00:45:00  acme  [review]  reviewing PR 42"
+        );
+    }
+
+    #[test]
+    fn formats_org_clock_minutes() {
+        let formatter = OrgClockFormatter::default();
+        assert_eq!(formatter.format(Duration::from_secs(90 * 60 + 59)), " 1:30");
+        assert_eq!(formatter.format(Duration::from_secs(36 * 3600)), "36:00");
+    }
+
+    #[test]
+    fn formats_compact_hours_and_minutes() {
+        assert_eq!(
+            CompactFormatter.format(Duration::from_secs(45 * 60 + 59)),
+            "45m"
+        );
+        assert_eq!(
+            CompactFormatter.format(Duration::from_secs(65 * 60)),
+            "1h05m"
+        );
+    }
 }
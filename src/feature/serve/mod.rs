@@ -0,0 +1,468 @@
+use std::net::IpAddr;
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use chrono_tz::Tz;
+use clap::ValueEnum;
+use error_stack::{Report, Result, ResultExt};
+use serde_json::{json, Value};
+use tiny_http::{Header, Response, Server};
+
+use super::{
+    metrics,
+    tracker::{
+        reporter::{GroupBy, ReportTimespan, Reporter},
+        ActiveSession, SessionDetails, StartupStatus, DEFAULT_TIMER,
+    },
+};
+use crate::common::start_of_day;
+
+#[derive(Debug, thiserror::Error)]
+#[error("server error")]
+pub struct ServeError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reply {
+    pub status: u16,
+    pub body: Body,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Body {
+    Json(Value),
+    /// the Prometheus text format of `/metrics`
+    Metrics(String),
+}
+
+impl Body {
+    /// The JSON of the body, `Null` for metrics.
+    pub fn json(&self) -> &Value {
+        match self {
+            Body::Json(value) => value,
+            Body::Metrics(_) => &Value::Null,
+        }
+    }
+}
+
+impl Reply {
+    fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            body: Body::Json(body),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::with_status(status, json!({ "error": message.into() }))
+    }
+
+    fn with_status(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            body: Body::Json(body),
+        }
+    }
+}
+
+/// Serves the JSON API on `bind` until the process is stopped.
+///
+/// Requests are handled one at a time with the same tracker the CLI uses, so the lockfile
+/// and database are read and written exactly as by the equivalent commands.
+pub fn run<T: Reporter>(mut tracker: T, bind: &str, tz: &Tz) -> Result<(), ServeError> {
+    let server = Server::http(bind)
+        .map_err(|error| Report::new(ServeError).attach_printable(error.to_string()))
+        .attach_printable_lazy(|| format!("unable to listen on {bind}"))?;
+    match server.server_addr().to_ip() {
+        Some(addr) => println!("Listening on http://{addr}"),
+        None => println!("Listening on {bind}"),
+    }
+    for mut request in server.incoming_requests() {
+        let header = |name: &'static str| {
+            request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv(name))
+                .map(|header| header.value.to_string())
+        };
+        let headers = RequestHeaders {
+            host: header("Host"),
+            origin: header("Origin"),
+            content_type: header("Content-Type"),
+        };
+        let method = request.method().as_str().to_string();
+        let mut body = String::new();
+        let reply = match guard(&method, &headers) {
+            Err(reply) => reply,
+            Ok(()) => match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => route(&mut tracker, &method, request.url(), &body, tz),
+                Err(_) => Reply::error(400, "the body is not UTF-8"),
+            },
+        };
+        let (content_type, body) = match reply.body {
+            Body::Json(value) => ("application/json", value.to_string()),
+            Body::Metrics(text) => (metrics::CONTENT_TYPE, text),
+        };
+        let header =
+            Header::from_bytes("Content-Type", content_type).expect("header is valid ASCII");
+        let response = Response::from_string(body)
+            .with_status_code(reply.status)
+            .with_header(header);
+        if let Err(error) = request.respond(response) {
+            tracing::warn!(%error, "unable to send response");
+        }
+    }
+    Ok(())
+}
+
+/// The headers [`guard`] looks at.
+#[derive(Debug, Clone, Default)]
+pub struct RequestHeaders {
+    pub host: Option<String>,
+    pub origin: Option<String>,
+    pub content_type: Option<String>,
+}
+
+/// Rejects requests that a web page open in a browser could make, since the API has no
+/// authentication.
+///
+/// - `Host` must name a loopback address, so a DNS rebinding page cannot reach the API
+/// - an `Origin`, which browsers send on cross-site requests, must be a loopback one too
+/// - `POST` needs `Content-Type: application/json`, which a plain cross-site form cannot send
+pub fn guard(method: &str, headers: &RequestHeaders) -> std::result::Result<(), Reply> {
+    if !headers.host.as_deref().is_some_and(is_loopback) {
+        return Err(Reply::error(403, "only requests to localhost are served"));
+    }
+    if let Some(origin) = &headers.origin {
+        let authority = origin
+            .strip_prefix("http://")
+            .or_else(|| origin.strip_prefix("https://"));
+        if !authority.is_some_and(is_loopback) {
+            return Err(Reply::error(403, format!("origin {origin} is not allowed")));
+        }
+    }
+    let media_type = headers
+        .content_type
+        .as_deref()
+        .and_then(|value| value.split(';').next())
+        .map(str::trim);
+    if method == "POST"
+        && !media_type.is_some_and(|media| media.eq_ignore_ascii_case("application/json"))
+    {
+        return Err(Reply::error(
+            415,
+            "POST requests need Content-Type: application/json",
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `authority`, a host with an optional port, is `localhost` or a loopback address.
+fn is_loopback(authority: &str) -> bool {
+    let host = match authority.strip_prefix('[') {
+        Some(rest) => match rest.split_once(']') {
+            Some((host, port)) if port.is_empty() || port.starts_with(':') => host,
+            _ => return false,
+        },
+        None => authority
+            .rsplit_once(':')
+            .map_or(authority, |(host, _)| host),
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Handles one request that passed [`guard`].
+///
+/// - `GET /status`: the running sessions
+/// - `POST /start`: starts the timer; the optional body holds project, tags and note
+/// - `POST /stop`: stops the timer
+/// - `GET /records?from&to`: records of the days `from` through `to` (YYYY-MM-DD)
+/// - `GET /report?group_by&from&to`: totals per `project` or `day`
+/// - `GET /metrics`: gauges in the Prometheus text format
+pub fn route<T: Reporter>(tracker: &mut T, method: &str, url: &str, body: &str, tz: &Tz) -> Reply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let query: Vec<(&str, &str)> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .collect();
+    let param = |name: &str| {
+        query
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    };
+    let result = match (method, path) {
+        ("GET", "/status") => status(tracker),
+        ("POST", "/start") => start(tracker, body),
+        ("POST", "/stop") => stop(tracker),
+        ("GET", "/records") => {
+            timespan(param("from"), param("to"), tz).and_then(|timespan| records(tracker, timespan))
+        }
+        ("GET", "/report") => timespan(param("from"), param("to"), tz)
+            .and_then(|timespan| report(tracker, timespan, param("group_by"), tz)),
+        ("GET", "/metrics") => metrics::render(tracker, DEFAULT_TIMER, Utc::now(), tz)
+            .map(|text| Reply {
+                status: 200,
+                body: Body::Metrics(text),
+            })
+            .map_err(internal),
+        (_, "/status" | "/start" | "/stop" | "/records" | "/report" | "/metrics") => Err(
+            Reply::error(405, format!("{method} is not allowed on {path}")),
+        ),
+        _ => Err(Reply::error(404, format!("no endpoint {path}"))),
+    };
+    result.unwrap_or_else(|reply| reply)
+}
+
+fn status<T: Reporter>(tracker: &T) -> std::result::Result<Reply, Reply> {
+    let sessions = tracker.active_sessions().map_err(internal)?;
+    let running = tracker.is_running().map_err(internal)?;
+    let now = Utc::now();
+    let sessions: Vec<_> = sessions
+        .iter()
+        .map(|session| session_json(session, now))
+        .collect();
+    Ok(Reply::ok(json!({
+        "running": running,
+        "sessions": sessions,
+    })))
+}
+
+fn session_json(session: &ActiveSession, now: DateTime<Utc>) -> Value {
+    let mut value = serde_json::to_value(&session.details).unwrap_or_else(|_| json!({}));
+    value["timer"] = json!(session.timer);
+    value["start"] = json!(session.start.datetime());
+    value["paused"] = json!(session.is_paused());
+    value["elapsed_secs"] = json!(session.elapsed(now).as_secs());
+    value
+}
+
+fn start<T: Reporter>(tracker: &mut T, body: &str) -> std::result::Result<Reply, Reply> {
+    let details: SessionDetails = match body.trim() {
+        "" => SessionDetails::default(),
+        body => serde_json::from_str(body)
+            .map_err(|error| Reply::error(400, format!("invalid session: {error}")))?,
+    };
+    match tracker.start_with(details).map_err(internal)? {
+        StartupStatus::Started => Ok(Reply::with_status(201, json!({ "started": true }))),
+        StartupStatus::Running(timer) => Err(Reply::error(
+            409,
+            format!("timer '{timer}' is already running"),
+        )),
+    }
+}
+
+fn stop<T: Reporter>(tracker: &mut T) -> std::result::Result<Reply, Reply> {
+    if !tracker.is_running().map_err(internal)? {
+        return Err(Reply::error(409, "no session is running"));
+    }
+    tracker.stop().map_err(internal)?;
+    Ok(Reply::ok(json!({ "stopped": true })))
+}
+
+fn records<T: Reporter>(
+    tracker: &T,
+    timespan: ReportTimespan,
+) -> std::result::Result<Reply, Reply> {
+    let records = tracker.records_in(timespan).map_err(internal)?;
+    Ok(Reply::ok(json!(records)))
+}
+
+fn report<T: Reporter>(
+    tracker: &T,
+    timespan: ReportTimespan,
+    group_by: Option<&str>,
+    tz: &Tz,
+) -> std::result::Result<Reply, Reply> {
+    let group_by = match group_by {
+        Some(name) => GroupBy::from_str(name, true)
+            .map_err(|_| Reply::error(400, format!("unknown group_by '{name}'")))?,
+        None => GroupBy::default(),
+    };
+    let groups: Vec<(String, u64)> = match group_by {
+        GroupBy::Project => tracker
+            .project_totals(timespan)
+            .map_err(internal)?
+            .into_iter()
+            .map(|(project, total)| (project.unwrap_or_default(), total.as_secs()))
+            .collect(),
+        GroupBy::Day => tracker
+            .daily_totals(timespan, tz)
+            .map_err(internal)?
+            .into_iter()
+            .map(|(day, total)| (day.to_string(), total.as_secs()))
+            .collect(),
+    };
+    let total: u64 = groups.iter().map(|(_, secs)| secs).sum();
+    let groups: Vec<_> = groups
+        .into_iter()
+        .map(|(key, secs)| json!({ "key": key, "total_secs": secs }))
+        .collect();
+    Ok(Reply::ok(json!({
+        "total_secs": total,
+        "groups": groups,
+    })))
+}
+
+/// The days `from` through `to` in `tz`; either end may be open.
+fn timespan(
+    from: Option<&str>,
+    to: Option<&str>,
+    tz: &Tz,
+) -> std::result::Result<ReportTimespan, Reply> {
+    let date = |text: &str| {
+        text.parse::<NaiveDate>().map_err(|_| {
+            Reply::error(
+                400,
+                format!("expected a date like 2026-10-19, got '{text}'"),
+            )
+        })
+    };
+    if from.is_none() && to.is_none() {
+        return Ok(ReportTimespan::All);
+    }
+    let from = match from {
+        Some(from) => start_of_day(date(from)?, tz),
+        None => DateTime::<Utc>::MIN_UTC,
+    };
+    let to = match to {
+        Some(to) => date(to)?
+            .checked_add_days(Days::new(1))
+            .map(|day| start_of_day(day, tz))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        None => DateTime::<Utc>::MAX_UTC,
+    };
+    Ok(ReportTimespan::Range { from, to })
+}
+
+fn internal<E>(report: Report<E>) -> Reply {
+    tracing::warn!("{report:?}");
+    Reply::error(500, "internal error, see the server log")
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+
+    use super::*;
+    use crate::feature::tracker::{flatfile::FlatFileTracker, Tracker};
+
+    fn tracker(dir: &TempDir) -> FlatFileTracker {
+        FlatFileTracker::new(dir.path().join("db.json"), dir.path().join("lock.json"))
+    }
+
+    #[test]
+    fn starts_and_stops_through_the_tracker() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = tracker(&dir);
+        let tz = chrono_tz::UTC;
+
+        let started = route(&mut tracker, "POST", "/start", r#"{"project":"web"}"#, &tz);
+        assert_eq!(started.status, 201);
+        assert_eq!(route(&mut tracker, "POST", "/start", "", &tz).status, 409);
+        let status = route(&mut tracker, "GET", "/status", "", &tz);
+        assert_eq!(status.body.json()["running"], true);
+        assert_eq!(status.body.json()["sessions"][0]["project"], "web");
+
+        assert_eq!(route(&mut tracker, "POST", "/stop", "", &tz).status, 200);
+        assert_eq!(route(&mut tracker, "POST", "/stop", "", &tz).status, 409);
+        assert_eq!(tracker.records().unwrap().count(), 1);
+        let records = route(&mut tracker, "GET", "/records", "", &tz);
+        assert_eq!(records.body.json()[0]["project"], "web");
+    }
+
+    #[test]
+    fn reports_totals_per_group() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = tracker(&dir);
+        let tz = chrono_tz::UTC;
+        route(&mut tracker, "POST", "/start", r#"{"project":"web"}"#, &tz);
+        route(&mut tracker, "POST", "/stop", "", &tz);
+
+        let report = route(&mut tracker, "GET", "/report?group_by=project", "", &tz);
+        assert_eq!(report.status, 200);
+        assert_eq!(report.body.json()["groups"][0]["key"], "web");
+
+        let today = Utc::now().date_naive();
+        let url = format!("/report?group_by=day&from={today}&to={today}");
+        let report = route(&mut tracker, "GET", &url, "", &tz);
+        assert_eq!(report.body.json()["groups"][0]["key"], today.to_string());
+    }
+
+    #[test]
+    fn serves_metrics_as_text() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = tracker(&dir);
+        let tz = chrono_tz::UTC;
+        route(&mut tracker, "POST", "/start", r#"{"project":"web"}"#, &tz);
+
+        let reply = route(&mut tracker, "GET", "/metrics", "", &tz);
+        assert_eq!(reply.status, 200);
+        let Body::Metrics(text) = reply.body else {
+            panic!("expected metrics, got {:?}", reply.body);
+        };
+        assert!(text.contains("track_running{timer=\"default\"} 1\n"));
+        assert!(text.contains("track_today_seconds{project=\"web\"}"));
+    }
+
+    #[test]
+    fn rejects_bad_requests() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = tracker(&dir);
+        let tz = chrono_tz::UTC;
+
+        let mut status = |method, url| route(&mut tracker, method, url, "", &tz).status;
+        assert_eq!(status("GET", "/nope"), 404);
+        assert_eq!(status("DELETE", "/status"), 405);
+        assert_eq!(status("GET", "/records?from=yesterday"), 400);
+        assert_eq!(status("GET", "/report?group_by=client"), 400);
+        assert_eq!(route(&mut tracker, "POST", "/start", "{", &tz).status, 400);
+    }
+
+    #[test]
+    fn only_serves_local_json_clients() {
+        let headers =
+            |host: &str, origin: Option<&str>, content_type: Option<&str>| RequestHeaders {
+                host: Some(host.to_string()),
+                origin: origin.map(str::to_string),
+                content_type: content_type.map(str::to_string),
+            };
+        let json = Some("application/json; charset=utf-8");
+
+        for host in [
+            "localhost",
+            "localhost:7878",
+            "127.0.0.1:7878",
+            "[::1]:7878",
+            "[::1]",
+        ] {
+            assert!(guard("GET", &headers(host, None, None)).is_ok(), "{host}");
+        }
+        assert!(guard(
+            "POST",
+            &headers("localhost", Some("http://localhost:3000"), json)
+        )
+        .is_ok());
+
+        let status = |method, headers| guard(method, &headers).unwrap_err().status;
+        assert_eq!(status("GET", RequestHeaders::default()), 403);
+        assert_eq!(status("GET", headers("evil.example", None, None)), 403);
+        assert_eq!(
+            status("GET", headers("127.0.0.1.evil.example", None, None)),
+            403
+        );
+        assert_eq!(
+            status(
+                "GET",
+                headers("localhost", Some("https://evil.example"), None)
+            ),
+            403
+        );
+        assert_eq!(status("GET", headers("localhost", Some("null"), None)), 403);
+        assert_eq!(status("POST", headers("localhost", None, None)), 415);
+        assert_eq!(
+            status("POST", headers("localhost", None, Some("text/plain"))),
+            415
+        );
+    }
+}
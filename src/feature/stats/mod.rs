@@ -0,0 +1,96 @@
+use chrono::TimeZone;
+
+use super::{
+    report_fmt::{DurationFormat, HMSFormatter},
+    tracker::reporter::Statistics,
+};
+
+/// Renders the statistics as an aligned two column table.
+pub fn render<Tz: TimeZone>(stats: &Statistics, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    if stats.sessions == 0 {
+        return "no records in this window\n".to_string();
+    }
+    let days = |count: u32| match count {
+        1 => "1 day".to_string(),
+        count => format!("{count} days"),
+    };
+    let time_of_day = |time: Option<chrono::NaiveTime>| {
+        time.map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_default()
+    };
+    let longest = stats
+        .longest
+        .as_ref()
+        .map(|record| {
+            format!(
+                "{}  {} {}",
+                HMSFormatter.format(record.duration()),
+                record.start.datetime().with_timezone(tz).format("%Y-%m-%d"),
+                record.project.as_deref().unwrap_or("-")
+            )
+        })
+        .unwrap_or_default();
+    let busiest = stats
+        .busiest_weekday
+        .map(|(weekday, total)| format!("{weekday} ({})", HMSFormatter.format(total)))
+        .unwrap_or_default();
+
+    let rows = [
+        ("sessions", stats.sessions.to_string()),
+        ("total", HMSFormatter.format(stats.total)),
+        ("mean session", HMSFormatter.format(stats.mean)),
+        ("median session", HMSFormatter.format(stats.median)),
+        ("longest session", longest),
+        ("average start", time_of_day(stats.average_start)),
+        ("average stop", time_of_day(stats.average_stop)),
+        ("days worked", stats.days_worked.to_string()),
+        ("current streak", days(stats.current_streak)),
+        ("longest streak", days(stats.longest_streak)),
+        ("busiest weekday", busiest),
+    ];
+    rows.iter()
+        .map(|(label, value)| format!("{label:16}{value}\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{NaiveTime, Utc, Weekday};
+
+    use super::*;
+
+    #[test]
+    fn empty_window_says_so() {
+        assert_eq!(
+            render(&Statistics::default(), &Utc),
+            "no records in this window\n"
+        );
+    }
+
+    #[test]
+    fn renders_one_row_per_figure() {
+        let stats = Statistics {
+            sessions: 3,
+            total: Duration::from_secs(3 * 3600),
+            average_start: NaiveTime::from_hms_opt(9, 5, 0),
+            current_streak: 1,
+            longest_streak: 4,
+            busiest_weekday: Some((Weekday::Tue, Duration::from_secs(7200))),
+            ..Default::default()
+        };
+
+        let text = render(&stats, &Utc);
+
+        assert_eq!(text.lines().count(), 11);
+        assert!(text.contains("sessions        3\n"));
+        assert!(text.contains("average start   09:05\n"));
+        assert!(text.contains("current streak  1 day\n"));
+        assert!(text.contains("longest streak  4 days\n"));
+        assert!(text.contains("busiest weekday Tue (02:00:00)\n"));
+    }
+}
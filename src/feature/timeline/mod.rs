@@ -0,0 +1,393 @@
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, TimeZone, Utc};
+use owo_colors::{AnsiColors, OwoColorize};
+
+use super::{
+    report_fmt::{DurationFormat, HMSFormatter},
+    tracker::TimeRecord,
+};
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+const MINUTES_PER_CELL: u32 = 30;
+const CELLS: usize = (MINUTES_PER_DAY / MINUTES_PER_CELL) as usize;
+const LABEL_WIDTH: usize = 12;
+const NO_PROJECT_LABEL: &str = "-";
+const PALETTE: [AnsiColors; 6] = [
+    AnsiColors::Cyan,
+    AnsiColors::Magenta,
+    AnsiColors::Yellow,
+    AnsiColors::Blue,
+    AnsiColors::Green,
+    AnsiColors::BrightCyan,
+];
+
+/// Minutes since local midnight, `start` inclusive and `end` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Span {
+    start: u32,
+    end: u32,
+}
+
+impl Span {
+    fn minutes(&self) -> u32 {
+        self.end - self.start
+    }
+
+    fn intersect(&self, other: &Span) -> Option<Span> {
+        let span = Span {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        };
+        (span.start < span.end).then_some(span)
+    }
+}
+
+struct Row<'a> {
+    label: &'a str,
+    color: AnsiColors,
+    spans: Vec<Span>,
+}
+
+/// Renders the records of `date` in `tz` as bars on a 24 hour axis.
+///
+/// Sessions crossing midnight are clipped to the day and breaks are left out of the bars.
+/// Bars and overlaps are plain characters when `color` is false.
+pub fn render<Tz: TimeZone>(
+    records: &[TimeRecord],
+    date: NaiveDate,
+    tz: &Tz,
+    color: bool,
+) -> String {
+    let mut records: Vec<_> = records.iter().collect();
+    records.sort_by_key(|rec| rec.start.timestamp_millis());
+    let rows: Vec<_> = records
+        .into_iter()
+        .filter_map(|record| {
+            let spans = work_spans(record, date, tz);
+            let label = record.project.as_deref().unwrap_or(NO_PROJECT_LABEL);
+            (!spans.is_empty()).then(|| Row {
+                label,
+                color: project_color(label),
+                spans,
+            })
+        })
+        .collect();
+
+    let mut text = format!("{}\n", date.format("%a %Y-%m-%d"));
+    text.push_str(&axis());
+    if rows.is_empty() {
+        text.push_str("no records\n");
+        return text;
+    }
+    for row in &rows {
+        let minutes = row.spans.iter().map(Span::minutes).sum();
+        let bar: String = (0..CELLS)
+            .map(|cell| match (covered(&row.spans, cell), color) {
+                (true, true) => "█".color(row.color).to_string(),
+                (true, false) => "█".to_string(),
+                (false, true) => "·".dimmed().to_string(),
+                (false, false) => "·".to_string(),
+            })
+            .collect();
+        text.push_str(&format!(
+            "{:LABEL_WIDTH$} {bar}  {}\n",
+            truncate(row.label),
+            format_minutes(minutes)
+        ));
+    }
+    text.push_str(&summary_row(&rows, color));
+
+    let overlaps = overlaps(&rows);
+    for gap in gaps(&rows) {
+        text.push_str(&format!("gap      {}\n", describe(gap)));
+    }
+    for overlap in overlaps {
+        let line = format!("overlap  {}", describe(overlap));
+        match color {
+            true => text.push_str(&format!("{}\n", line.red())),
+            false => text.push_str(&format!("{line}\n")),
+        }
+    }
+    text
+}
+
+/// Spans worked on `date`: the record minus its breaks, clipped to the day.
+fn work_spans<Tz: TimeZone>(record: &TimeRecord, date: NaiveDate, tz: &Tz) -> Vec<Span> {
+    let Some(day) = day_bounds(date, tz) else {
+        return Vec::new();
+    };
+    record
+        .work_spans()
+        .into_iter()
+        .filter_map(|(start, end)| clip(start, end, day, date, tz))
+        .collect()
+}
+
+fn day_bounds<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start_of = |date: NaiveDate| {
+        tz.from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|start| start.with_timezone(&Utc))
+    };
+    Some((
+        start_of(date)?,
+        start_of(date.checked_add_days(Days::new(1))?)?,
+    ))
+}
+
+fn clip<Tz: TimeZone>(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    (day_start, day_end): (DateTime<Utc>, DateTime<Utc>),
+    date: NaiveDate,
+    tz: &Tz,
+) -> Option<Span> {
+    let start = start.max(day_start);
+    let end = end.min(day_end);
+    if start >= end {
+        return None;
+    }
+    let minute_of_day = |instant: DateTime<Utc>| {
+        if instant == day_end {
+            return MINUTES_PER_DAY;
+        }
+        let local = instant.with_timezone(tz).naive_local();
+        let minutes = (local - date.and_time(NaiveTime::MIN)).num_minutes();
+        minutes.clamp(0, MINUTES_PER_DAY.into()) as u32
+    };
+    let span = Span {
+        start: minute_of_day(start),
+        end: minute_of_day(end),
+    };
+    (span.start < span.end).then_some(span)
+}
+
+fn covered(spans: &[Span], cell: usize) -> bool {
+    let cell = cell_span(cell);
+    spans.iter().any(|span| span.intersect(&cell).is_some())
+}
+
+fn cell_span(cell: usize) -> Span {
+    let start = cell as u32 * MINUTES_PER_CELL;
+    Span {
+        start,
+        end: start + MINUTES_PER_CELL,
+    }
+}
+
+/// One bar for the whole day: worked, idle, or more than one session at once.
+fn summary_row(rows: &[Row], color: bool) -> String {
+    let bar: String = (0..CELLS)
+        .map(|cell| {
+            let (shade, colored) = match rows.iter().filter(|row| covered(&row.spans, cell)).count()
+            {
+                0 => ("·", "·".dimmed().to_string()),
+                1 => ("█", "█".to_string()),
+                _ => ("▓", "▓".red().to_string()),
+            };
+            match color {
+                true => colored,
+                false => shade.to_string(),
+            }
+        })
+        .collect();
+    let worked = merge(rows.iter().flat_map(|row| row.spans.clone()).collect());
+    let minutes = worked.iter().map(Span::minutes).sum();
+    format!(
+        "{:LABEL_WIDTH$} {bar}  {}\n",
+        "all",
+        format_minutes(minutes)
+    )
+}
+
+fn merge(mut spans: Vec<Span>) -> Vec<Span> {
+    spans.sort();
+    let mut merged: Vec<Span> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// Idle time between the first and the last session of the day.
+fn gaps(rows: &[Row]) -> Vec<Span> {
+    let worked = merge(rows.iter().flat_map(|row| row.spans.clone()).collect());
+    worked
+        .windows(2)
+        .map(|pair| Span {
+            start: pair[0].end,
+            end: pair[1].start,
+        })
+        .collect()
+}
+
+/// Time covered by more than one record.
+fn overlaps(rows: &[Row]) -> Vec<Span> {
+    let mut overlaps = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        for other in &rows[index + 1..] {
+            for span in &row.spans {
+                overlaps.extend(other.spans.iter().filter_map(|o| span.intersect(o)));
+            }
+        }
+    }
+    merge(overlaps)
+}
+
+fn axis() -> String {
+    let mut labels = vec![' '; CELLS + 2];
+    for hour in (0..=24).step_by(3) {
+        let cell = (hour * 60 / MINUTES_PER_CELL) as usize;
+        for (offset, ch) in hour.to_string().chars().enumerate() {
+            if let Some(slot) = labels.get_mut(cell + offset) {
+                *slot = ch;
+            }
+        }
+    }
+    let labels: String = labels.into_iter().collect();
+    format!("{:LABEL_WIDTH$} {}\n", "", labels.trim_end())
+}
+
+fn describe(span: Span) -> String {
+    format!(
+        "{}-{}  {}",
+        clock(span.start),
+        clock(span.end),
+        format_minutes(span.minutes())
+    )
+}
+
+fn clock(minute_of_day: u32) -> String {
+    format!("{:02}:{:02}", minute_of_day / 60, minute_of_day % 60)
+}
+
+fn format_minutes(minutes: u32) -> String {
+    HMSFormatter.format(std::time::Duration::from_secs(u64::from(minutes) * 60))
+}
+
+fn truncate(label: &str) -> String {
+    label.chars().take(LABEL_WIDTH).collect()
+}
+
+/// Stable color per project name.
+fn project_color(project: &str) -> AnsiColors {
+    let hash = project.bytes().fold(0usize, |hash, byte| {
+        hash.wrapping_mul(31) + usize::from(byte)
+    });
+    PALETTE[hash % PALETTE.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use chrono_tz::Tz;
+
+    use super::*;
+    use crate::feature::tracker::{Break, SessionDetails};
+
+    const BERLIN: Tz = chrono_tz::Europe::Berlin;
+
+    fn record(start: (u32, u32, u32), end: (u32, u32, u32), project: &str) -> TimeRecord {
+        let at = |(day, hour, minute)| {
+            BERLIN
+                .with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let details = SessionDetails {
+            project: Some(project.to_string()),
+            ..Default::default()
+        };
+        TimeRecord::new(at(start).into(), at(end).into(), details)
+    }
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+    }
+
+    #[test]
+    fn clips_sessions_crossing_midnight_in_the_configured_timezone() {
+        let before = record((17, 23, 0), (18, 1, 30), "night");
+        let after = record((18, 23, 0), (19, 2, 0), "late");
+
+        assert_eq!(
+            work_spans(&before, day(), &BERLIN),
+            vec![Span { start: 0, end: 90 }]
+        );
+        assert_eq!(
+            work_spans(&after, day(), &BERLIN),
+            vec![Span {
+                start: 23 * 60,
+                end: MINUTES_PER_DAY
+            }]
+        );
+        // in UTC the first session ended the evening before
+        assert!(work_spans(&before, day(), &Utc).is_empty());
+    }
+
+    #[test]
+    fn breaks_split_the_bar() {
+        let mut record = record((18, 9, 0), (18, 12, 0), "acme");
+        record.breaks = vec![Break {
+            start: BERLIN
+                .with_ymd_and_hms(2026, 10, 18, 10, 0, 0)
+                .unwrap()
+                .with_timezone(&Utc),
+            end: BERLIN
+                .with_ymd_and_hms(2026, 10, 18, 10, 30, 0)
+                .unwrap()
+                .with_timezone(&Utc),
+        }];
+
+        assert_eq!(
+            work_spans(&record, day(), &BERLIN),
+            vec![
+                Span {
+                    start: 540,
+                    end: 600
+                },
+                Span {
+                    start: 630,
+                    end: 720
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_gaps_and_overlaps() {
+        let records = [
+            record((18, 9, 0), (18, 10, 30), "acme"),
+            record((18, 10, 0), (18, 11, 0), "beta"),
+            record((18, 13, 0), (18, 14, 0), "acme"),
+        ];
+
+        let text = render(&records, day(), &BERLIN, true);
+
+        assert!(text.starts_with("Sun 2026-10-18\n"));
+        assert!(text.contains("gap      11:00-13:00  02:00:00\n"));
+        assert!(text.contains("overlap  10:00-10:30  00:30:00"));
+    }
+
+    #[test]
+    fn empty_day_says_so() {
+        let text = render(&[], day(), &BERLIN, false);
+
+        assert!(text.ends_with("no records\n"));
+    }
+
+    #[test]
+    fn plain_output_has_no_escapes() {
+        let records = [
+            record((18, 9, 0), (18, 10, 30), "acme"),
+            record((18, 10, 0), (18, 11, 0), "beta"),
+        ];
+
+        let text = render(&records, day(), &BERLIN, false);
+
+        assert!(!text.contains('\x1b'));
+        assert!(text.contains("overlap  10:00-10:30  00:30:00\n"));
+    }
+}
@@ -0,0 +1,224 @@
+use std::{collections::BTreeMap, fmt, iter, ops, time::Duration};
+
+use serde::Deserialize;
+
+use super::TimeRecord;
+
+/// An amount of money in minor units, e.g. cents.
+///
+/// Read from the config as a decimal number with at most two places, e.g. `99.5`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(try_from = "f64")]
+pub struct Money(pub i64);
+
+impl Money {
+    /// Taking `self` as an hourly rate, the amount for `time`, rounded half away from zero
+    /// to the minor unit.
+    pub fn for_time(self, time: Duration) -> Money {
+        Money(div_round(
+            i128::from(self.0) * i128::from(time.as_secs()),
+            3600,
+        ))
+    }
+}
+
+impl TryFrom<f64> for Money {
+    type Error = String;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        hundredths(value).map(Money)
+    }
+}
+
+impl ops::Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0.saturating_add(other.0))
+    }
+}
+
+impl ops::AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+impl iter::Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::default(), ops::Add::add)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let units = self.0.unsigned_abs();
+        write!(f, "{sign}{}.{:02}", units / 100, units % 100)
+    }
+}
+
+/// `value` times 100 if that is a whole number.
+pub(crate) fn hundredths(value: f64) -> Result<i64, String> {
+    let scaled = (value * 100.0).round();
+    if !scaled.is_finite() || (scaled - value * 100.0).abs() > 1e-6 || scaled.abs() > 1e15 {
+        return Err(format!("{value} has more than two decimal places"));
+    }
+    Ok(scaled as i64)
+}
+
+/// `numerator / denominator` rounded half away from zero.
+pub(crate) fn div_round(numerator: i128, denominator: i128) -> i64 {
+    let half = denominator / 2;
+    let rounded = match numerator < 0 {
+        true => (numerator - half) / denominator,
+        false => (numerator + half) / denominator,
+    };
+    i64::try_from(rounded).unwrap_or(if rounded < 0 { i64::MIN } else { i64::MAX })
+}
+
+/// Clients, their projects and hourly rates, read from the `billing` section of the config.
+///
+/// A rate set for a tag beats one set for the project, which beats the client's rate.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateTable {
+    /// currency of clients that do not set their own
+    pub currency: Option<String>,
+    pub clients: BTreeMap<String, Client>,
+    /// hourly rate per project
+    pub projects: BTreeMap<String, Money>,
+    /// hourly rate per tag
+    pub tags: BTreeMap<String, Money>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Client {
+    /// hourly rate of the client's projects
+    pub rate: Option<Money>,
+    pub currency: Option<String>,
+    pub projects: Vec<String>,
+}
+
+/// The client and hourly rate that apply to one record.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rate {
+    pub client: Option<String>,
+    pub hourly: Option<Money>,
+    pub currency: Option<String>,
+}
+
+impl RateTable {
+    /// The client owning `project`, if any.
+    pub fn client_of(&self, project: Option<&str>) -> Option<(&str, &Client)> {
+        let project = project?;
+        self.clients
+            .iter()
+            .find(|(_, client)| client.projects.iter().any(|name| name == project))
+            .map(|(name, client)| (name.as_str(), client))
+    }
+
+    /// The most specific rate for `record`: first matching tag, then project, then client.
+    pub fn rate_for(&self, record: &TimeRecord) -> Rate {
+        let client = self.client_of(record.project.as_deref());
+        let tag_rate = record.tags.iter().find_map(|tag| self.tags.get(tag));
+        let project_rate = record
+            .project
+            .as_ref()
+            .and_then(|project| self.projects.get(project));
+        let client_rate = client.and_then(|(_, client)| client.rate.as_ref());
+        Rate {
+            client: client.map(|(name, _)| name.to_string()),
+            hourly: tag_rate.or(project_rate).or(client_rate).copied(),
+            currency: client
+                .and_then(|(_, client)| client.currency.clone())
+                .or_else(|| self.currency.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::feature::tracker::SessionDetails;
+
+    fn table() -> RateTable {
+        serde_json::from_str(
+            r#"{
+                "currency": "EUR",
+                "clients": {
+                    "acme": {"rate": 100, "currency": "USD", "projects": ["website", "api"]},
+                    "globex": {"projects": ["intranet"]}
+                },
+                "projects": {"api": 120},
+                "tags": {"urgent": 150}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn record(project: &str, tags: &[&str]) -> TimeRecord {
+        let details = SessionDetails {
+            project: Some(project.to_string()),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        };
+        TimeRecord::new(Utc::now().into(), Utc::now().into(), details)
+    }
+
+    #[test]
+    fn most_specific_rate_wins() {
+        let table = table();
+
+        assert_eq!(
+            table.rate_for(&record("website", &[])).hourly,
+            Some(Money(10000))
+        );
+        assert_eq!(
+            table.rate_for(&record("api", &[])).hourly,
+            Some(Money(12000))
+        );
+        assert_eq!(
+            table.rate_for(&record("api", &["urgent"])).hourly,
+            Some(Money(15000))
+        );
+    }
+
+    #[test]
+    fn rates_are_whole_minor_units() {
+        let rates: RateTable = serde_json::from_str(r#"{"projects":{"web":99.95}}"#).unwrap();
+
+        assert_eq!(rates.projects["web"], Money(9995));
+        assert!(serde_json::from_str::<RateTable>(r#"{"projects":{"web":0.001}}"#).is_err());
+    }
+
+    #[test]
+    fn amounts_round_half_away_from_zero() {
+        let minutes = |minutes: u64| Duration::from_secs(minutes * 60);
+
+        // 0.10 for 15 minutes is 0.025
+        assert_eq!(Money(10).for_time(minutes(15)), Money(3));
+        assert_eq!(Money(-10).for_time(minutes(15)), Money(-3));
+        assert_eq!(Money(9995).for_time(minutes(90)), Money(14993));
+        assert_eq!(Money(-5).to_string(), "-0.05");
+        assert_eq!(Money(123450).to_string(), "1234.50");
+    }
+
+    #[test]
+    fn currency_falls_back_to_the_default() {
+        let table = table();
+
+        let acme = table.rate_for(&record("website", &[]));
+        let globex = table.rate_for(&record("intranet", &[]));
+        let unknown = table.rate_for(&record("hobby", &[]));
+
+        assert_eq!(acme.client.as_deref(), Some("acme"));
+        assert_eq!(acme.currency.as_deref(), Some("USD"));
+        assert_eq!(globex.currency.as_deref(), Some("EUR"));
+        assert_eq!(globex.hourly, None);
+        assert_eq!(unknown.client, None);
+    }
+}
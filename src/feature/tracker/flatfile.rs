@@ -1,22 +1,79 @@
 use super::{
-    reporter::Reporter, EndTime, StartTime, StartupStatus, TimeRecord, Tracker, TrackerError,
+    assign_legacy_ids, reporter::Reporter, ActiveSession, Break, EndTime, PauseStatus, RecordId,
+    ResumeStatus, SessionDetails, StartTime, StartupStatus, Task, TimeRecord, Tracker,
+    TrackerError, DEFAULT_TIMER,
 };
-use error_stack::{Result, ResultExt};
+use chrono::{DateTime, NaiveDate, Utc};
+use error_stack::{Report, Result, ResultExt};
 use serde::{Deserialize, Serialize};
 use std::{
-    fs::OpenOptions,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fs::{File, OpenOptions},
     io::{Read, Write},
     path::{Path, PathBuf},
+    rc::Rc,
+    time::SystemTime,
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct LockfileData {
     start_time: StartTime,
+    #[serde(default)]
+    details: SessionDetails,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    breaks: Vec<Break>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paused_at: Option<DateTime<Utc>>,
+}
+
+impl LockfileData {
+    fn session(&self, timer: &str) -> ActiveSession {
+        ActiveSession {
+            timer: timer.to_string(),
+            start: self.start_time,
+            details: self.details.clone(),
+            breaks: self.breaks.clone(),
+            paused_at: self.paused_at,
+        }
+    }
+}
+
+/// All running timers, keyed by name.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct Lockfile {
+    timers: BTreeMap<String, LockfileData>,
+}
+
+// lockfiles written before named timers held a single session
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LockfileFormat {
+    Timers(Lockfile),
+    Single(LockfileData),
+}
+
+impl From<LockfileFormat> for Lockfile {
+    fn from(value: LockfileFormat) -> Self {
+        match value {
+            LockfileFormat::Timers(lockfile) => lockfile,
+            LockfileFormat::Single(data) => Self {
+                timers: BTreeMap::from([(DEFAULT_TIMER.to_string(), data)]),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 struct FlatfileDatabase {
     records: Vec<TimeRecord>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    days_off: BTreeSet<NaiveDate>,
+    /// number of the last invoice issued
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_invoice: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tasks: Vec<Task>,
 }
 
 impl FlatfileDatabase {
@@ -29,9 +86,20 @@ impl FlatfileDatabase {
 #[error("filesystem tracker error")]
 pub struct FlatFileTrackerError;
 
+#[derive(Clone)]
 pub struct FlatFileTracker {
     db: PathBuf,
     lockfile: PathBuf,
+    timer: String,
+    /// the database as last read or written, when kept in memory
+    cache: Option<Rc<RefCell<Cached>>>,
+}
+
+#[derive(Debug, Clone)]
+struct Cached {
+    db: FlatfileDatabase,
+    /// modification time and length of the file `db` was read from or written to
+    stamp: Option<(SystemTime, u64)>,
 }
 impl Reporter for FlatFileTracker {}
 
@@ -43,74 +111,291 @@ impl FlatFileTracker {
     {
         let db = db.into();
         let lockfile = lockfile.into();
-        Self { db, lockfile }
+        Self {
+            db,
+            lockfile,
+            timer: DEFAULT_TIMER.to_string(),
+            cache: None,
+        }
     }
 
-    fn start_impl(&self) -> Result<StartupStatus, FlatFileTrackerError> {
-        if self.is_running() {
-            return Ok(StartupStatus::Running);
-        }
-        let lockfile_data = {
-            let start_time = StartTime::now();
-            let data = LockfileData { start_time };
-            serde_json::to_string(&data)
-                .change_context(FlatFileTrackerError)
-                .attach_printable("failed to serialize lockfile data")?
+    /// Keeps the database in memory and answers reads from there, as long as the file's
+    /// modification time and length stay the same.
+    ///
+    /// Writes still go to disk, and a file changed by another process is read again. Clones
+    /// share the loaded database.
+    pub fn in_memory(mut self) -> Result<Self, TrackerError> {
+        let stamp = file_stamp(&self.db);
+        let db = load_database(&self.db).change_context(TrackerError)?;
+        self.cache = Some(Rc::new(RefCell::new(Cached { db, stamp })));
+        Ok(self)
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db
+    }
+
+    pub fn lockfile_path(&self) -> &Path {
+        &self.lockfile
+    }
+
+    /// Operate on the named timer instead of the default one.
+    pub fn with_timer<T: Into<String>>(mut self, timer: T) -> Self {
+        self.timer = timer.into();
+        self
+    }
+
+    fn load_database(&self) -> Result<FlatfileDatabase, FlatFileTrackerError> {
+        let Some(cache) = &self.cache else {
+            return load_database(&self.db);
         };
-        OpenOptions::new()
+        let stamp = file_stamp(&self.db);
+        if cache.borrow().stamp != stamp {
+            let db = load_database(&self.db)?;
+            *cache.borrow_mut() = Cached { db, stamp };
+        }
+        Ok(cache.borrow().db.clone())
+    }
+
+    fn save_database(&self, db: FlatfileDatabase) -> Result<(), FlatFileTrackerError> {
+        save_database(&self.db, &db)?;
+        if let Some(cache) = &self.cache {
+            let stamp = file_stamp(&self.db);
+            *cache.borrow_mut() = Cached { db, stamp };
+        }
+        Ok(())
+    }
+
+    /// Takes an exclusive lock on the file next to the lockfile, held until the returned
+    /// file is dropped.
+    ///
+    /// Every read-modify-write of the lockfile happens under it, so commands on different
+    /// timers cannot overwrite each other's entries.
+    fn lock_timers(&self) -> Result<File, FlatFileTrackerError> {
+        let mut name = self.lockfile.file_name().unwrap_or_default().to_owned();
+        name.push(".guard");
+        let guard = self.lockfile.with_file_name(name);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
             .write(true)
-            .create_new(true)
-            .open(&self.lockfile)
+            .open(&guard)
             .change_context(FlatFileTrackerError)
-            .attach_printable("unable to create new lockfile")?
-            .write_all(lockfile_data.as_bytes())
+            .attach_printable_lazy(|| format!("unable to open {}", guard.display()))?;
+        file.lock()
             .change_context(FlatFileTrackerError)
-            .attach_printable("failed to write lockfile data")?;
+            .attach_printable_lazy(|| format!("unable to lock {}", guard.display()))?;
+        Ok(file)
+    }
+
+    fn start_impl(&self, details: SessionDetails) -> Result<StartupStatus, FlatFileTrackerError> {
+        let _guard = self.lock_timers()?;
+        let mut lock = read_lockfile(&self.lockfile)?;
+        if lock.timers.contains_key(&self.timer) {
+            return Ok(StartupStatus::Running(self.timer.clone()));
+        }
+        let data = LockfileData {
+            start_time: StartTime::now(),
+            details,
+            breaks: Vec::new(),
+            paused_at: None,
+        };
+        lock.timers.insert(self.timer.clone(), data);
+        write_lockfile(&self.lockfile, &lock)?;
         Ok(StartupStatus::Started)
     }
 
     fn stop_impl(&self) -> Result<(), FlatFileTrackerError> {
-        let start = read_lockfile(&self.lockfile)?;
+        let _guard = self.lock_timers()?;
+        let mut lock = read_lockfile(&self.lockfile)?;
+        let data = lock
+            .timers
+            .remove(&self.timer)
+            .ok_or(FlatFileTrackerError)
+            .attach_printable_lazy(|| format!("timer '{}' is not running", self.timer))?;
+
+        let record = data.session(&self.timer).finish(EndTime::now());
+        let mut db = self.load_database()?;
+        db.push(record);
+        self.save_database(db)?;
 
-        let end = EndTime::now();
+        write_lockfile(&self.lockfile, &lock)
+    }
 
-        let record = TimeRecord { start, end };
-        let mut db = load_database(&self.db)?;
-        db.push(record);
-        save_database(&self.db, db)?;
+    /// Applies `update` to the running session of this tracker's timer.
+    fn update_session<T>(
+        &self,
+        update: impl FnOnce(&mut LockfileData) -> T,
+    ) -> Result<T, FlatFileTrackerError> {
+        let _guard = self.lock_timers()?;
+        let mut lock = read_lockfile(&self.lockfile)?;
+        let data = lock
+            .timers
+            .get_mut(&self.timer)
+            .ok_or(FlatFileTrackerError)
+            .attach_printable_lazy(|| format!("timer '{}' is not running", self.timer))?;
+        let result = update(data);
+        write_lockfile(&self.lockfile, &lock)?;
+        Ok(result)
+    }
 
-        std::fs::remove_file(&self.lockfile)
-            .change_context(FlatFileTrackerError)
-            .attach_printable("unable to remove lockfile")?;
-        Ok(())
+    fn add_note_impl(&self, note: &str) -> Result<(), FlatFileTrackerError> {
+        self.update_session(|data| data.details.append_note(note))
+    }
+
+    fn pause_impl(&self) -> Result<PauseStatus, FlatFileTrackerError> {
+        self.update_session(|data| {
+            if data.paused_at.is_some() {
+                return PauseStatus::AlreadyPaused;
+            }
+            data.paused_at = Some(Utc::now());
+            PauseStatus::Paused
+        })
+    }
+
+    fn resume_impl(&self) -> Result<ResumeStatus, FlatFileTrackerError> {
+        self.update_session(|data| {
+            let Some(start) = data.paused_at.take() else {
+                return ResumeStatus::NotPaused;
+            };
+            data.breaks.push(Break {
+                start,
+                end: Utc::now(),
+            });
+            ResumeStatus::Resumed
+        })
     }
 }
 
 impl Tracker for FlatFileTracker {
-    fn start(&mut self) -> Result<StartupStatus, TrackerError> {
-        self.start_impl().change_context(TrackerError)
+    fn start_with(&mut self, details: SessionDetails) -> Result<StartupStatus, TrackerError> {
+        self.start_impl(details).change_context(TrackerError)
     }
 
-    fn is_running(&self) -> bool {
-        self.lockfile.exists()
+    fn is_running(&self) -> Result<bool, TrackerError> {
+        let lock = read_lockfile(&self.lockfile).change_context(TrackerError)?;
+        Ok(lock.timers.contains_key(&self.timer))
+    }
+
+    fn active_sessions(&self) -> Result<Vec<ActiveSession>, TrackerError> {
+        let lock = read_lockfile(&self.lockfile).change_context(TrackerError)?;
+        let sessions = lock
+            .timers
+            .iter()
+            .map(|(timer, data)| data.session(timer))
+            .collect();
+        Ok(sessions)
     }
 
     fn stop(&mut self) -> Result<(), TrackerError> {
         self.stop_impl().change_context(TrackerError)
     }
 
+    fn pause(&mut self) -> Result<PauseStatus, TrackerError> {
+        self.pause_impl().change_context(TrackerError)
+    }
+
+    fn resume(&mut self) -> Result<ResumeStatus, TrackerError> {
+        self.resume_impl().change_context(TrackerError)
+    }
+
+    fn add_note(&mut self, note: &str) -> Result<(), TrackerError> {
+        self.add_note_impl(note).change_context(TrackerError)
+    }
+
     fn records(&self) -> Result<impl Iterator<Item = TimeRecord>, TrackerError> {
-        let db = load_database(&self.db).change_context(TrackerError)?;
+        let db = self.load_database().change_context(TrackerError)?;
 
         Ok(db.records.into_iter())
     }
+
+    fn add_records(&mut self, records: Vec<TimeRecord>) -> Result<(), TrackerError> {
+        let mut db = self.load_database().change_context(TrackerError)?;
+        db.records.extend(records);
+        self.save_database(db).change_context(TrackerError)
+    }
+
+    fn update_record(&mut self, record: TimeRecord) -> Result<(), TrackerError> {
+        let mut db = self.load_database().change_context(TrackerError)?;
+        let stored = db
+            .records
+            .iter_mut()
+            .find(|rec| rec.id == record.id)
+            .ok_or(TrackerError)
+            .attach_printable_lazy(|| format!("no record with id {}", record.id))?;
+        *stored = record;
+        self.save_database(db).change_context(TrackerError)
+    }
+
+    fn remove_record(&mut self, id: RecordId) -> Result<(), TrackerError> {
+        let mut db = self.load_database().change_context(TrackerError)?;
+        let count = db.records.len();
+        db.records.retain(|rec| rec.id != id);
+        if db.records.len() == count {
+            return Err(
+                Report::new(TrackerError).attach_printable(format!("no record with id {id}"))
+            );
+        }
+        self.save_database(db).change_context(TrackerError)
+    }
+
+    fn days_off(&self) -> Result<BTreeSet<NaiveDate>, TrackerError> {
+        let db = self.load_database().change_context(TrackerError)?;
+        Ok(db.days_off)
+    }
+
+    fn set_day_off(&mut self, date: NaiveDate, off: bool) -> Result<(), TrackerError> {
+        let mut db = self.load_database().change_context(TrackerError)?;
+        match off {
+            true => db.days_off.insert(date),
+            false => db.days_off.remove(&date),
+        };
+        self.save_database(db).change_context(TrackerError)
+    }
+
+    fn issue_invoice(&mut self, ids: &[RecordId]) -> Result<u32, TrackerError> {
+        let mut db = self.load_database().change_context(TrackerError)?;
+        for id in ids {
+            let record = db
+                .records
+                .iter()
+                .find(|rec| rec.id == *id)
+                .ok_or(TrackerError)
+                .attach_printable_lazy(|| format!("no record with id {id}"))?;
+            if let Some(number) = record.invoice {
+                return Err(Report::new(TrackerError)
+                    .attach_printable(format!("record {id} is already on invoice {number}")));
+            }
+        }
+        let number = db.last_invoice.unwrap_or_default() + 1;
+        for record in db.records.iter_mut().filter(|rec| ids.contains(&rec.id)) {
+            record.invoice = Some(number);
+        }
+        db.last_invoice = Some(number);
+        self.save_database(db).change_context(TrackerError)?;
+        Ok(number)
+    }
+
+    fn tasks(&self) -> Result<Vec<Task>, TrackerError> {
+        let db = self.load_database().change_context(TrackerError)?;
+        Ok(db.tasks)
+    }
+
+    fn save_task(&mut self, task: Task) -> Result<(), TrackerError> {
+        let mut db = self.load_database().change_context(TrackerError)?;
+        match db.tasks.iter_mut().find(|stored| stored.name == task.name) {
+            Some(stored) => *stored = task,
+            None => db.tasks.push(task),
+        }
+        self.save_database(db).change_context(TrackerError)
+    }
 }
 
-fn save_database<P>(path: P, db: FlatfileDatabase) -> Result<(), FlatFileTrackerError>
+fn save_database<P>(path: P, db: &FlatfileDatabase) -> Result<(), FlatFileTrackerError>
 where
     P: AsRef<Path>,
 {
-    let db = serde_json::to_string(&db)
+    let db = serde_json::to_string(db)
         .change_context(FlatFileTrackerError)
         .attach_printable("failed to serialize database data")?;
     OpenOptions::new()
@@ -126,6 +411,12 @@ where
     Ok(())
 }
 
+/// Modification time and length of `path`, `None` if it does not exist.
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
 fn load_database<P>(db: P) -> Result<FlatfileDatabase, FlatFileTrackerError>
 where
     P: AsRef<Path>,
@@ -150,25 +441,56 @@ where
         return Ok(FlatfileDatabase::default());
     }
 
-    let res = serde_json::from_str(&db_buf)
+    let mut res: FlatfileDatabase = serde_json::from_str(&db_buf)
         .change_context(FlatFileTrackerError)
         .attach_printable("unable to deserialize database data")?;
+    assign_legacy_ids(&mut res.records);
     Ok(res)
 }
 
-fn read_lockfile<P>(lockfile: P) -> Result<StartTime, FlatFileTrackerError>
+fn read_lockfile<P>(lockfile: P) -> Result<Lockfile, FlatFileTrackerError>
 where
     P: AsRef<Path>,
 {
+    if !lockfile.as_ref().exists() {
+        return Ok(Lockfile::default());
+    }
     let file = OpenOptions::new()
         .read(true)
         .open(lockfile.as_ref())
         .change_context(FlatFileTrackerError)
         .attach_printable("unable to open lockfile")?;
-    let data: LockfileData = serde_json::from_reader(file)
+    let data: LockfileFormat = serde_json::from_reader(file)
         .change_context(FlatFileTrackerError)
         .attach_printable("unable to deserialize lockfile data")?;
-    Ok(data.start_time)
+    Ok(data.into())
+}
+
+/// Writes the running timers, removing the lockfile once none are left.
+fn write_lockfile<P>(lockfile: P, lock: &Lockfile) -> Result<(), FlatFileTrackerError>
+where
+    P: AsRef<Path>,
+{
+    if lock.timers.is_empty() {
+        std::fs::remove_file(lockfile.as_ref())
+            .change_context(FlatFileTrackerError)
+            .attach_printable("unable to remove lockfile")?;
+        return Ok(());
+    }
+    let lockfile_data = serde_json::to_string(lock)
+        .change_context(FlatFileTrackerError)
+        .attach_printable("failed to serialize lockfile data")?;
+    // readers do not take the guard, so they must never see a half-written file
+    let mut name = lockfile.as_ref().file_name().unwrap_or_default().to_owned();
+    name.push(".tmp");
+    let temp = lockfile.as_ref().with_file_name(name);
+    std::fs::write(&temp, lockfile_data)
+        .change_context(FlatFileTrackerError)
+        .attach_printable("failed to write lockfile data")?;
+    std::fs::rename(&temp, lockfile.as_ref())
+        .change_context(FlatFileTrackerError)
+        .attach_printable("failed to replace lockfile")?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -190,7 +512,7 @@ mod tests {
 
         tracker.start().unwrap();
 
-        assert!(tracker.is_running());
+        assert!(tracker.is_running().unwrap());
     }
 
     #[test]
@@ -201,7 +523,7 @@ mod tests {
         tracker.start().unwrap();
         tracker.stop().unwrap();
 
-        assert!(!tracker.is_running());
+        assert!(!tracker.is_running().unwrap());
     }
 
     #[test]
@@ -239,6 +561,230 @@ mod tests {
 
         //Then  astarted state is returned
 
-        assert_eq!(started, StartupStatus::Running);
+        assert_eq!(started, StartupStatus::Running(DEFAULT_TIMER.to_string()));
+    }
+
+    #[test]
+    fn notes_are_appended_to_the_running_session() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let mut tracker = FlatFileTracker::new(db, lockfile);
+        let details = SessionDetails {
+            note: Some("reviewing PR 42".to_string()),
+            ..Default::default()
+        };
+
+        tracker.start_with(details).unwrap();
+        tracker.add_note("left comments").unwrap();
+        tracker.stop().unwrap();
+
+        let record = tracker.records().unwrap().next().unwrap();
+        assert_eq!(
+            record.note.as_deref(),
+            Some("reviewing PR 42; left comments")
+        );
+    }
+
+    #[test]
+    fn adding_a_note_fails_when_not_running() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let mut tracker = FlatFileTracker::new(db, lockfile);
+
+        assert!(tracker.add_note("nothing to annotate").is_err());
+    }
+
+    #[test]
+    fn breaks_are_kept_on_the_record_and_excluded_from_duration() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let mut tracker = FlatFileTracker::new(db, lockfile);
+
+        tracker.start().unwrap();
+        assert_eq!(tracker.pause().unwrap(), PauseStatus::Paused);
+        assert_eq!(tracker.pause().unwrap(), PauseStatus::AlreadyPaused);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert_eq!(tracker.resume().unwrap(), ResumeStatus::Resumed);
+        assert_eq!(tracker.resume().unwrap(), ResumeStatus::NotPaused);
+        tracker.pause().unwrap();
+        tracker.stop().unwrap();
+
+        let record = tracker.records().unwrap().next().unwrap();
+        assert_eq!(record.breaks.len(), 2);
+        assert!(record.gross_duration() >= std::time::Duration::from_millis(20));
+        assert!(record.duration() < record.gross_duration());
+    }
+
+    #[test]
+    fn named_timers_run_concurrently() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let mut meeting = FlatFileTracker::new(&db, &lockfile).with_timer("meeting");
+        let mut build = FlatFileTracker::new(&db, &lockfile).with_timer("build-wait");
+
+        meeting.start().unwrap();
+        build.start().unwrap();
+        assert_eq!(
+            meeting.start().unwrap(),
+            StartupStatus::Running("meeting".to_string())
+        );
+        let timers: Vec<_> = build
+            .active_sessions()
+            .unwrap()
+            .into_iter()
+            .map(|session| session.timer)
+            .collect();
+        assert_eq!(timers, vec!["build-wait", "meeting"]);
+
+        meeting.stop().unwrap();
+        assert!(!meeting.is_running().unwrap());
+        assert!(build.is_running().unwrap());
+        build.stop().unwrap();
+        assert!(!lockfile.exists());
+        assert_eq!(build.records().unwrap().count(), 2);
+    }
+
+    #[test]
+    fn concurrent_starts_of_different_timers_are_all_kept() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let threads: Vec<_> = (0..8)
+            .map(|index| {
+                let (db, lockfile) = (db.clone(), lockfile.clone());
+                std::thread::spawn(move || {
+                    FlatFileTracker::new(db, lockfile)
+                        .with_timer(format!("t{index}"))
+                        .start()
+                        .unwrap()
+                })
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), StartupStatus::Started);
+        }
+
+        let tracker = FlatFileTracker::new(&db, &lockfile);
+        assert_eq!(tracker.active_sessions().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn a_corrupt_lockfile_is_an_error() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        std::fs::write(&lockfile, "{not json").unwrap();
+        let tracker = FlatFileTracker::new(db, lockfile);
+
+        assert!(tracker.is_running().is_err());
+    }
+
+    #[test]
+    fn reads_single_session_lockfiles() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        std::fs::write(
+            &lockfile,
+            r#"{"start_time":"2026-10-18T09:00:00Z","details":{"project":"acme"}}"#,
+        )
+        .unwrap();
+        let mut tracker = FlatFileTracker::new(db, lockfile);
+
+        assert!(tracker.is_running().unwrap());
+        tracker.stop().unwrap();
+
+        let record = tracker.records().unwrap().next().unwrap();
+        assert_eq!(record.project.as_deref(), Some("acme"));
+    }
+
+    #[test]
+    fn records_can_be_edited_and_removed_by_id() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let mut tracker = FlatFileTracker::new(db, lockfile);
+        tracker.start().unwrap();
+        tracker.stop().unwrap();
+        tracker.start().unwrap();
+        tracker.stop().unwrap();
+
+        let mut records: Vec<_> = tracker.records().unwrap().collect();
+        records[0].project = Some("acme".to_string());
+        tracker.update_record(records[0].clone()).unwrap();
+        tracker.remove_record(records[1].id).unwrap();
+
+        let stored: Vec<_> = tracker.records().unwrap().collect();
+        assert_eq!(stored, vec![records[0].clone()]);
+        assert!(tracker.remove_record(records[1].id).is_err());
+    }
+
+    #[test]
+    fn records_without_ids_keep_the_same_id_across_loads() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let record =
+            r#"{"start":"2026-09-01T09:00:00Z","end":"2026-09-01T10:00:00Z","project":"acme"}"#;
+        std::fs::write(&db, format!(r#"{{"records":[{record},{record}]}}"#)).unwrap();
+        let mut tracker = FlatFileTracker::new(&db, lockfile);
+
+        let ids: Vec<_> = tracker.records().unwrap().map(|rec| rec.id).collect();
+        assert_ne!(ids[0], ids[1]);
+        let reloaded: Vec<_> = tracker.records().unwrap().map(|rec| rec.id).collect();
+        assert_eq!(reloaded, ids);
+
+        assert_eq!(tracker.issue_invoice(&ids[..1]).unwrap(), 1);
+        let saved: Vec<_> = load_database(&db).unwrap().records;
+        assert_eq!(saved.iter().map(|rec| rec.id).collect::<Vec<_>>(), ids);
+        assert_eq!(saved[0].invoice, Some(1));
+    }
+
+    #[test]
+    fn in_memory_database_is_read_again_after_outside_changes() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let mut cached = FlatFileTracker::new(&db, &lockfile).in_memory().unwrap();
+        cached.start().unwrap();
+        cached.stop().unwrap();
+        assert_eq!(cached.records().unwrap().count(), 1);
+
+        let mut other = FlatFileTracker::new(&db, &lockfile);
+        other.start().unwrap();
+        other.stop().unwrap();
+
+        assert_eq!(cached.records().unwrap().count(), 2);
+        cached.start().unwrap();
+        cached.stop().unwrap();
+        assert_eq!(other.records().unwrap().count(), 3);
+    }
+
+    #[test]
+    fn invoices_are_numbered_and_bill_records_once() {
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let mut tracker = FlatFileTracker::new(db, lockfile);
+        for _ in 0..3 {
+            tracker.start().unwrap();
+            tracker.stop().unwrap();
+        }
+        let ids: Vec<_> = tracker.records().unwrap().map(|rec| rec.id).collect();
+
+        assert_eq!(tracker.issue_invoice(&ids[..2]).unwrap(), 1);
+        assert!(tracker.issue_invoice(&ids[1..]).is_err());
+        assert_eq!(tracker.issue_invoice(&ids[2..]).unwrap(), 2);
+
+        let invoices: Vec<_> = tracker.records().unwrap().map(|rec| rec.invoice).collect();
+        assert_eq!(invoices, vec![Some(1), Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn csv_export_round_trips_database_records() {
+        use crate::feature::export::{csv, Exporter};
+
+        let (_tempdir, lockfile, db) = tracking_paths();
+        let mut tracker = FlatFileTracker::new(&db, lockfile);
+        let details = SessionDetails {
+            project: Some("acme".to_string()),
+            tags: vec!["meeting".to_string()],
+            note: Some("weekly sync".to_string()),
+            non_billable: true,
+            task: None,
+        };
+        tracker.start_with(details).unwrap();
+        tracker.stop().unwrap();
+        tracker.start().unwrap();
+        tracker.stop().unwrap();
+
+        let stored = load_database(&db).unwrap().records;
+        let mut out = Vec::new();
+        csv::CsvExporter.export(&stored, &mut out).unwrap();
+        let parsed = csv::read_records(out.as_slice()).unwrap();
+
+        assert_eq!(parsed, stored);
     }
 }
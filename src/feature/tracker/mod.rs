@@ -1,10 +1,64 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use error_stack::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+pub mod billing;
 pub mod flatfile;
 pub mod reporter;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RecordId(Uuid);
+
+impl RecordId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Placeholder for records stored before ids existed, see [`RecordId::legacy`].
+    fn missing() -> Self {
+        Self(Uuid::nil())
+    }
+
+    fn is_missing(&self) -> bool {
+        self.0.is_nil()
+    }
+
+    /// The id of a record stored without one, derived from its times and project so that it
+    /// stays the same on every load until it is saved. `occurrence` tells apart records that
+    /// match in all of these.
+    fn legacy(record: &TimeRecord, occurrence: usize) -> Self {
+        let name = format!(
+            "{}/{}/{}/{occurrence}",
+            record.start.timestamp_millis(),
+            record.end.timestamp_millis(),
+            record.project.as_deref().unwrap_or_default()
+        );
+        Self(Uuid::new_v5(&Uuid::NAMESPACE_OID, name.as_bytes()))
+    }
+}
+
+impl Default for RecordId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for RecordId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for RecordId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct EndTime(DateTime<Utc>);
 
 impl EndTime {
@@ -14,9 +68,18 @@ impl EndTime {
     pub fn timestamp_millis(&self) -> i64 {
         self.0.timestamp_millis()
     }
+    pub fn datetime(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl From<DateTime<Utc>> for EndTime {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self(value)
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct StartTime(DateTime<Utc>);
 impl StartTime {
     pub fn now() -> Self {
@@ -26,29 +89,310 @@ impl StartTime {
     pub fn timestamp_millis(&self) -> i64 {
         self.0.timestamp_millis()
     }
+    pub fn datetime(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl From<DateTime<Utc>> for StartTime {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self(value)
+    }
+}
+
+/// What a session is about; set when tracking starts and copied onto the record.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// time that is not charged to the client
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub non_billable: bool,
+    /// name of the estimated task being worked on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// A pause taken inside a session.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Break {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Break {
+    pub fn duration(&self) -> std::time::Duration {
+        (self.end - self.start).to_std().unwrap_or_default()
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TimeRecord {
+    // records written before ids existed get one on load, which is persisted on the next save
+    #[serde(default = "RecordId::missing")]
+    pub id: RecordId,
     pub start: StartTime,
     pub end: EndTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub breaks: Vec<Break>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub non_billable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// number of the invoice that billed this record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invoice: Option<u32>,
+}
+
+impl SessionDetails {
+    pub fn append_note(&mut self, note: &str) {
+        self.note = Some(match self.note.take() {
+            Some(existing) => format!("{existing}; {note}"),
+            None => note.to_string(),
+        });
+    }
+}
+
+/// Gives records stored without an id their [`RecordId::legacy`] one.
+pub(crate) fn assign_legacy_ids(records: &mut [TimeRecord]) {
+    let mut seen: HashMap<(i64, i64, Option<String>), usize> = HashMap::new();
+    for record in records.iter_mut().filter(|record| record.id.is_missing()) {
+        let key = (
+            record.start.timestamp_millis(),
+            record.end.timestamp_millis(),
+            record.project.clone(),
+        );
+        let occurrence = seen.entry(key).or_default();
+        record.id = RecordId::legacy(record, *occurrence);
+        *occurrence += 1;
+    }
+}
+
+impl TimeRecord {
+    pub fn new(start: StartTime, end: EndTime, details: SessionDetails) -> Self {
+        Self {
+            id: RecordId::new(),
+            start,
+            end,
+            project: details.project,
+            tags: details.tags,
+            note: details.note,
+            breaks: Vec::new(),
+            non_billable: details.non_billable,
+            task: details.task,
+            invoice: None,
+        }
+    }
+
+    pub fn is_billable(&self) -> bool {
+        !self.non_billable
+    }
+
+    pub fn with_breaks(mut self, breaks: Vec<Break>) -> Self {
+        self.breaks = breaks;
+        self
+    }
+
+    /// Case-insensitive search in the note.
+    pub fn note_contains(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        self.note
+            .as_deref()
+            .is_some_and(|note| note.to_lowercase().contains(&text))
+    }
+
+    /// Time worked, i.e. the gross duration without breaks.
+    pub fn duration(&self) -> std::time::Duration {
+        self.gross_duration()
+            .saturating_sub(self.breaks.iter().map(Break::duration).sum())
+    }
+
+    pub fn gross_duration(&self) -> std::time::Duration {
+        let ms = self.end.timestamp_millis() - self.start.timestamp_millis();
+        std::time::Duration::from_millis(ms.max(0) as u64)
+    }
+
+    /// Start and end of each stretch worked between the breaks, in order.
+    pub fn work_spans(&self) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let end = self.end.datetime();
+        let mut breaks = self.breaks.clone();
+        breaks.sort_by_key(|pause| pause.start);
+        let mut spans = Vec::new();
+        let mut cursor = self.start.datetime();
+        for pause in breaks {
+            if pause.start.min(end) > cursor {
+                spans.push((cursor, pause.start.min(end)));
+            }
+            cursor = cursor.max(pause.end);
+        }
+        if end > cursor {
+            spans.push((cursor, end));
+        }
+        spans
+    }
+}
+
+/// A piece of work with an estimate; sessions refer to it by name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub estimate: std::time::Duration,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed: Option<DateTime<Utc>>,
+}
+
+impl Task {
+    pub fn is_open(&self) -> bool {
+        self.closed.is_none()
+    }
+}
+
+/// Timer used when no name is given.
+pub const DEFAULT_TIMER: &str = "default";
+
+/// A session that has been started and not yet stopped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveSession {
+    pub timer: String,
+    pub start: StartTime,
+    pub details: SessionDetails,
+    pub breaks: Vec<Break>,
+    pub paused_at: Option<DateTime<Utc>>,
+}
+
+impl ActiveSession {
+    /// Time worked so far, not counting breaks.
+    pub fn elapsed(&self, now: DateTime<Utc>) -> std::time::Duration {
+        self.finish(EndTime::from(now)).duration()
+    }
+
+    /// Time worked between `from` and `now`, not counting breaks.
+    pub fn elapsed_since(&self, from: DateTime<Utc>, now: DateTime<Utc>) -> std::time::Duration {
+        let start = self.start.datetime().max(from);
+        let overlap = |from: DateTime<Utc>, to: DateTime<Utc>| {
+            (to.min(now) - from.max(start)).to_std().unwrap_or_default()
+        };
+        let pending = self.paused_at.map(|paused_at| overlap(paused_at, now));
+        let breaks: std::time::Duration = self
+            .breaks
+            .iter()
+            .map(|pause| overlap(pause.start, pause.end))
+            .chain(pending)
+            .sum();
+        overlap(start, now).saturating_sub(breaks)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// The record this session becomes when stopped at `end`; a pending break ends with it.
+    pub fn finish(&self, end: EndTime) -> TimeRecord {
+        let mut breaks = self.breaks.clone();
+        if let Some(paused_at) = self.paused_at {
+            breaks.push(Break {
+                start: paused_at,
+                end: end.datetime(),
+            });
+        }
+        TimeRecord::new(self.start, end, self.details.clone()).with_breaks(breaks)
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StartupStatus {
-    Running,
+    /// the named timer was already running
+    Running(String),
     Started,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PauseStatus {
+    Paused,
+    AlreadyPaused,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ResumeStatus {
+    Resumed,
+    NotPaused,
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("filesystem tracker error")]
 pub struct TrackerError;
 pub trait Tracker {
-    fn start(&mut self) -> Result<StartupStatus, TrackerError>;
+    fn start(&mut self) -> Result<StartupStatus, TrackerError> {
+        self.start_with(SessionDetails::default())
+    }
 
-    fn is_running(&self) -> bool;
+    fn start_with(&mut self, details: SessionDetails) -> Result<StartupStatus, TrackerError>;
+
+    /// Whether this tracker's timer is running; fails if the running timers cannot be read.
+    fn is_running(&self) -> Result<bool, TrackerError>;
+
+    /// Every running timer, not only the one this tracker operates on.
+    fn active_sessions(&self) -> Result<Vec<ActiveSession>, TrackerError>;
 
     fn stop(&mut self) -> Result<(), TrackerError>;
 
+    /// Stops the running session and starts one with `details` in its place, or just starts
+    /// one if the timer is not running.
+    fn switch(&mut self, details: SessionDetails) -> Result<StartupStatus, TrackerError> {
+        if self.is_running()? {
+            self.stop()?;
+        }
+        self.start_with(details)
+    }
+
+    /// Starts a break in the running session.
+    fn pause(&mut self) -> Result<PauseStatus, TrackerError>;
+
+    /// Ends the break of the running session.
+    fn resume(&mut self) -> Result<ResumeStatus, TrackerError>;
+
+    /// Appends to the note of the running session.
+    fn add_note(&mut self, note: &str) -> Result<(), TrackerError>;
+
     fn records(&self) -> Result<impl Iterator<Item = TimeRecord>, TrackerError>;
+
+    /// Stores finished records, e.g. from an import.
+    fn add_records(&mut self, records: Vec<TimeRecord>) -> Result<(), TrackerError>;
+
+    /// Replaces the stored record that has the same id.
+    fn update_record(&mut self, record: TimeRecord) -> Result<(), TrackerError>;
+
+    fn remove_record(&mut self, id: RecordId) -> Result<(), TrackerError>;
+
+    /// Vacation and other days without expected work.
+    fn days_off(&self) -> Result<BTreeSet<NaiveDate>, TrackerError>;
+
+    /// Marks `date` as a day off, or as a regular day again when `off` is false.
+    fn set_day_off(&mut self, date: NaiveDate, off: bool) -> Result<(), TrackerError>;
+
+    /// Takes the next invoice number and marks the records as billed by it.
+    ///
+    /// Fails without changes if a record is unknown or already invoiced.
+    fn issue_invoice(&mut self, ids: &[RecordId]) -> Result<u32, TrackerError>;
+
+    fn tasks(&self) -> Result<Vec<Task>, TrackerError>;
+
+    /// Adds the task, or replaces the stored one with the same name.
+    fn save_task(&mut self, task: Task) -> Result<(), TrackerError>;
 }
@@ -1,11 +1,402 @@
-use chrono::Utc;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeZone, Timelike, Utc, Weekday};
 use error_stack::Result;
 use error_stack::ResultExt;
-use std::{ time::Duration};
+use serde::{Deserialize, Deserializer};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    f64::consts::TAU,
+    path::PathBuf,
+    time::Duration,
+};
 
-use super::Tracker;
+use super::{
+    billing::{Money, RateTable},
+    Break, StartTime, TimeRecord, Tracker,
+};
+use crate::common::{deserialize_duration, parse_duration, start_of_day};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ReportTimespan {
     Last(Duration),
+    /// Records starting at or after `from` and before `to`.
+    Range {
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    },
+    All,
+}
+
+impl ReportTimespan {
+    /// The period of the same length that ends where this one starts.
+    ///
+    /// `None` for `All` and for ranges without a lower or upper bound.
+    pub fn preceding(&self) -> Option<ReportTimespan> {
+        let (from, to) = match *self {
+            ReportTimespan::Last(length) => {
+                let now = Utc::now();
+                (now - chrono::Duration::from_std(length).ok()?, now)
+            }
+            ReportTimespan::Range { from, to } => (from, to),
+            ReportTimespan::All => return None,
+        };
+        let length = to.signed_duration_since(from);
+        let start = from.checked_sub_signed(length)?;
+        (from != DateTime::<Utc>::MIN_UTC && to != DateTime::<Utc>::MAX_UTC).then_some(
+            ReportTimespan::Range {
+                from: start,
+                to: from,
+            },
+        )
+    }
+
+    pub fn contains(&self, start: &StartTime) -> bool {
+        match self {
+            ReportTimespan::Last(timespan) => {
+                let target = (Utc::now() - *timespan).timestamp_millis();
+                start.timestamp_millis() >= target
+            }
+            ReportTimespan::Range { from, to } => {
+                let start = start.datetime();
+                start >= *from && start < *to
+            }
+            ReportTimespan::All => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum GroupBy {
+    Day,
+    #[default]
+    Project,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Day,
+    /// Monday to Sunday
+    Week,
+    Month,
+}
+
+impl Period {
+    /// Start and end of the period containing `date` in `tz`.
+    pub fn bounds(self, date: NaiveDate, tz: &chrono_tz::Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+        let (first, next) = match self {
+            Period::Day => (date, date + Days::new(1)),
+            Period::Week => {
+                let monday = date - Days::new(date.weekday().num_days_from_monday().into());
+                (monday, monday + Days::new(7))
+            }
+            Period::Month => {
+                let first = date.with_day(1).unwrap_or(date);
+                (first, first + chrono::Months::new(1))
+            }
+        };
+        (start_of_day(first, tz), start_of_day(next, tz))
+    }
+}
+
+/// A target amount of time per day or week, optionally for a single project.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Goal {
+    /// `day` or `week`
+    #[serde(deserialize_with = "deserialize_goal_period")]
+    pub period: Period,
+    /// e.g. `"8h"` or `"37h30m"`
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub target: Duration,
+    /// count only records of this project
+    #[serde(default)]
+    pub project: Option<String>,
+    /// weekdays a daily goal applies to, every day when unset
+    #[serde(default)]
+    pub days: Option<Vec<Weekday>>,
+}
+
+fn deserialize_goal_period<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Period, D::Error> {
+    match Period::deserialize(deserializer)? {
+        Period::Month => Err(serde::de::Error::custom(
+            "goals are per day or week, not per month",
+        )),
+        period => Ok(period),
+    }
+}
+
+impl Goal {
+    pub fn applies_on(&self, date: NaiveDate) -> bool {
+        match (&self.period, &self.days) {
+            (Period::Day, Some(days)) => days.contains(&date.weekday()),
+            _ => true,
+        }
+    }
+
+    fn counts(&self, project: Option<&str>) -> bool {
+        self.project.is_none() || self.project.as_deref() == project
+    }
+}
+
+/// Working time expected per weekday, the basis of the flextime balance.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schedule {
+    /// first day of the balance
+    pub since: NaiveDate,
+    /// e.g. `{"Mon": "8h", "Fri": "6h"}`; weekdays left out expect no work
+    #[serde(deserialize_with = "deserialize_hours")]
+    pub hours: HashMap<Weekday, Duration>,
+    /// file listing public holidays, relative to the config file
+    #[serde(default)]
+    pub holidays: Option<PathBuf>,
+}
+
+impl Schedule {
+    /// Time expected on `date` by the weekly schedule alone.
+    pub fn expected_on(&self, date: NaiveDate) -> Duration {
+        self.hours.get(&date.weekday()).copied().unwrap_or_default()
+    }
+}
+
+fn deserialize_hours<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<HashMap<Weekday, Duration>, D::Error> {
+    HashMap::<Weekday, String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(day, text)| {
+            Ok((
+                day,
+                parse_duration(&text).map_err(serde::de::Error::custom)?,
+            ))
+        })
+        .collect()
+}
+
+/// A fixed amount of time for a project, e.g. of a fixed-price contract.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Budget {
+    /// e.g. `"40h"`
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub limit: Duration,
+    /// percentages of the limit that `track start` warns about once passed
+    #[serde(default = "default_alerts")]
+    pub alerts: Vec<u32>,
+}
+
+fn default_alerts() -> Vec<u32> {
+    vec![80, 100]
+}
+
+/// Expected and tracked time of one day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayBalance {
+    pub date: NaiveDate,
+    pub expected: Duration,
+    pub actual: Duration,
+}
+
+impl DayBalance {
+    /// Overtime when positive, undertime when negative.
+    pub fn difference(&self) -> chrono::Duration {
+        to_signed(self.actual) - to_signed(self.expected)
+    }
+}
+
+fn to_signed(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::max_value())
+}
+
+/// Time of one client and project at one rate.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BillingLine {
+    pub client: Option<String>,
+    pub project: Option<String>,
+    pub rate: Option<Money>,
+    pub currency: Option<String>,
+    pub billable: Duration,
+    pub non_billable: Duration,
+}
+
+impl BillingLine {
+    /// Billable hours times the rate, rounded to the minor unit; `None` without a rate.
+    pub fn amount(&self) -> Option<Money> {
+        Some(self.rate?.for_time(self.billable))
+    }
+}
+
+/// Summary figures over the records of a timespan.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Statistics {
+    pub sessions: usize,
+    pub total: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    pub longest: Option<TimeRecord>,
+    /// circular mean of the local times of day sessions started
+    pub average_start: Option<NaiveTime>,
+    /// circular mean of the local times of day sessions stopped
+    pub average_stop: Option<NaiveTime>,
+    pub days_worked: usize,
+    /// consecutive tracked days up to today, or up to yesterday if today has nothing yet
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    /// weekday with the most tracked time
+    pub busiest_weekday: Option<(Weekday, Duration)>,
+}
+
+/// Time worked per calendar day in `tz`, for records that run past midnight.
+fn split_at_midnight(record: &TimeRecord, tz: &chrono_tz::Tz) -> Vec<(NaiveDate, Duration)> {
+    let (start, end) = (record.start.datetime(), record.end.datetime());
+    let mut days = Vec::new();
+    let mut date = start.with_timezone(tz).date_naive();
+    let mut from = start;
+    while from < end {
+        let to = start_of_day(date + Days::new(1), tz).min(end);
+        let overlap = |pause: &Break| {
+            (pause.end.min(to) - pause.start.max(from))
+                .to_std()
+                .unwrap_or_default()
+        };
+        let paused: Duration = record.breaks.iter().map(overlap).sum();
+        let gross = (to - from).to_std().unwrap_or_default();
+        days.push((date, gross.saturating_sub(paused)));
+        from = to;
+        date = date + Days::new(1);
+    }
+    days
+}
+
+fn median(sorted: &[Duration]) -> Duration {
+    match sorted.len() {
+        0 => Duration::ZERO,
+        len if len % 2 == 0 => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+        len => sorted[len / 2],
+    }
+}
+
+/// Circular mean, so that 23:00 and 01:00 average to midnight rather than noon.
+///
+/// `None` without times, or when they spread evenly around the clock.
+fn average_time_of_day(times: impl Iterator<Item = NaiveTime>) -> Option<NaiveTime> {
+    const DAY: f64 = 86_400.0;
+    let (count, sin, cos) = times.fold((0u32, 0.0, 0.0), |(count, sin, cos), time| {
+        let angle = f64::from(time.num_seconds_from_midnight()) / DAY * TAU;
+        (count + 1, sin + angle.sin(), cos + angle.cos())
+    });
+    if count == 0 || f64::hypot(sin, cos) < 1e-6 * f64::from(count) {
+        return None;
+    }
+    let seconds = (f64::atan2(sin, cos).rem_euclid(TAU) / TAU * DAY).round() as u32;
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds % 86_400, 0)
+}
+
+/// Longest run of consecutive days, and the run ending today or yesterday.
+fn streaks(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> (u32, u32) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        run = match previous {
+            Some(previous) if previous.succ_opt() == Some(*day) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*day);
+    }
+
+    let mut current = 0;
+    let mut day = match days.contains(&today) {
+        true => Some(today),
+        false => today.pred_opt(),
+    };
+    while let Some(date) = day.filter(|date| days.contains(date)) {
+        current += 1;
+        day = date.pred_opt();
+    }
+    (current, longest)
+}
+
+/// How far a goal is met in the period containing the time it was computed at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// tracked so far, including running sessions
+    pub done: Duration,
+    /// whether a matching session is running and not paused
+    pub running: bool,
+}
+
+impl GoalProgress {
+    pub fn remaining(&self) -> Duration {
+        self.goal.target.saturating_sub(self.done)
+    }
+
+    pub fn fraction(&self) -> f64 {
+        if self.goal.target.is_zero() {
+            return 1.0;
+        }
+        self.done.as_secs_f64() / self.goal.target.as_secs_f64()
+    }
+
+    /// When the target is reached if the running session keeps going.
+    pub fn finish_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let remaining = self.remaining();
+        (self.running && !remaining.is_zero())
+            .then(|| now + chrono::Duration::from_std(remaining).unwrap_or_default())
+    }
+
+    /// Total at the end of the period if work continues at the pace so far.
+    pub fn projected(&self, now: DateTime<Utc>) -> Duration {
+        let elapsed = (now.min(self.to) - self.from).num_seconds();
+        let length = (self.to - self.from).num_seconds();
+        if elapsed <= 0 {
+            return self.done;
+        }
+        self.done.mul_f64(length as f64 / elapsed as f64)
+    }
+}
+
+/// Time spent on a budgeted project so far.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetUsage {
+    pub project: String,
+    pub budget: Budget,
+    /// tracked time per week, keyed by the Monday the week starts on
+    pub weeks: BTreeMap<NaiveDate, Duration>,
+}
+
+impl BudgetUsage {
+    pub fn used(&self) -> Duration {
+        self.weeks.values().sum()
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.budget.limit.saturating_sub(self.used())
+    }
+
+    pub fn fraction(&self) -> f64 {
+        if self.budget.limit.is_zero() {
+            return 1.0;
+        }
+        self.used().as_secs_f64() / self.budget.limit.as_secs_f64()
+    }
+
+    /// The highest alert percentage that has been passed.
+    pub fn alert(&self) -> Option<u32> {
+        let percent = self.fraction() * 100.0;
+        self.budget
+            .alerts
+            .iter()
+            .copied()
+            .filter(|alert| percent >= f64::from(*alert))
+            .max()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -13,28 +404,258 @@ pub enum ReportTimespan {
 pub struct ReporterError;
 
 pub trait Reporter: Tracker {
-     fn total_duration(&self, timespan: ReportTimespan) -> Result<Duration, ReporterError> {
-        match timespan {
-            ReportTimespan::Last(timespan) => {
-                let target = (Utc::now() - timespan).timestamp_millis();
-
-                let total_ms = self
-                    .records()
-                    .change_context(ReporterError)
-                    .attach("failed to query records")?
-                    .filter_map(|rec| {
-                        if rec.start.timestamp_millis() >= target {
-                            let ms = rec.end.timestamp_millis() - rec.start.timestamp_millis();
-                            Some(ms)
-                        } else {
-                            None
-                        }
-                    })
-                    .sum::<i64>();
-
-                Ok(Duration::from_millis(total_ms as u64))
+    fn records_in(&self, timespan: ReportTimespan) -> Result<Vec<TimeRecord>, ReporterError> {
+        let records = self
+            .records()
+            .change_context(ReporterError)
+            .attach("failed to query records")?
+            .filter(|rec| timespan.contains(&rec.start))
+            .collect();
+        Ok(records)
+    }
+
+    fn total_duration(&self, timespan: ReportTimespan) -> Result<Duration, ReporterError> {
+        let total = self
+            .records_in(timespan)?
+            .iter()
+            .map(TimeRecord::duration)
+            .sum();
+        Ok(total)
+    }
+
+    /// Time worked per calendar day in `tz`; a record counts towards the day it started.
+    fn daily_totals<Tz: TimeZone>(
+        &self,
+        timespan: ReportTimespan,
+        tz: &Tz,
+    ) -> Result<BTreeMap<NaiveDate, Duration>, ReporterError> {
+        let mut totals = BTreeMap::new();
+        for record in self.records_in(timespan)? {
+            let day = record.start.datetime().with_timezone(tz).date_naive();
+            *totals.entry(day).or_default() += record.duration();
+        }
+        Ok(totals)
+    }
+
+    /// Time per project, records without a project under `None`.
+    fn project_totals(
+        &self,
+        timespan: ReportTimespan,
+    ) -> Result<BTreeMap<Option<String>, Duration>, ReporterError> {
+        let mut totals = BTreeMap::new();
+        for record in self.records_in(timespan)? {
+            *totals.entry(record.project.clone()).or_default() += record.duration();
+        }
+        Ok(totals)
+    }
+
+    /// Billable and non-billable time per client, project and rate, ordered by client and project.
+    fn billing(
+        &self,
+        timespan: ReportTimespan,
+        rates: &RateTable,
+    ) -> Result<Vec<BillingLine>, ReporterError> {
+        let mut lines: Vec<BillingLine> = Vec::new();
+        for record in self.records_in(timespan)? {
+            let rate = rates.rate_for(&record);
+            let position = lines.iter().position(|line| {
+                line.client == rate.client
+                    && line.project == record.project
+                    && line.rate == rate.hourly
+                    && line.currency == rate.currency
+            });
+            let line = match position {
+                Some(index) => &mut lines[index],
+                None => {
+                    lines.push(BillingLine {
+                        client: rate.client,
+                        project: record.project.clone(),
+                        rate: rate.hourly,
+                        currency: rate.currency,
+                        ..Default::default()
+                    });
+                    lines.last_mut().expect("line was just pushed")
+                }
+            };
+            match record.is_billable() {
+                true => line.billable += record.duration(),
+                false => line.non_billable += record.duration(),
             }
         }
+        lines.sort_by(|a, b| (&a.client, &a.project, a.rate).cmp(&(&b.client, &b.project, b.rate)));
+        Ok(lines)
+    }
+
+    /// All time tracked on each budgeted project, split into weeks in `tz`.
+    fn budget_usage<Tz: TimeZone>(
+        &self,
+        budgets: &BTreeMap<String, Budget>,
+        tz: &Tz,
+    ) -> Result<Vec<BudgetUsage>, ReporterError> {
+        let mut usage: Vec<_> = budgets
+            .iter()
+            .map(|(project, budget)| BudgetUsage {
+                project: project.clone(),
+                budget: budget.clone(),
+                weeks: BTreeMap::new(),
+            })
+            .collect();
+        for record in self.records_in(ReportTimespan::All)? {
+            let Some(project) = usage
+                .iter_mut()
+                .find(|usage| record.project.as_ref() == Some(&usage.project))
+            else {
+                continue;
+            };
+            let date = record.start.datetime().with_timezone(tz).date_naive();
+            let monday = date - Days::new(date.weekday().num_days_from_monday().into());
+            *project.weeks.entry(monday).or_default() += record.duration();
+        }
+        Ok(usage)
+    }
+
+    /// All time tracked on the task named `task`.
+    fn task_time(&self, task: &str) -> Result<Duration, ReporterError> {
+        Ok(self
+            .records_in(ReportTimespan::All)?
+            .iter()
+            .filter(|rec| rec.task.as_deref() == Some(task))
+            .map(TimeRecord::duration)
+            .sum())
+    }
+
+    /// Session and day statistics for `timespan`, with days and times of day in `tz`.
+    fn statistics<Tz: TimeZone>(
+        &self,
+        timespan: ReportTimespan,
+        today: NaiveDate,
+        tz: &Tz,
+    ) -> Result<Statistics, ReporterError> {
+        let records = self.records_in(timespan)?;
+        if records.is_empty() {
+            return Ok(Statistics::default());
+        }
+        let mut durations: Vec<_> = records.iter().map(TimeRecord::duration).collect();
+        durations.sort();
+        let total: Duration = durations.iter().sum();
+        let local = |instant: DateTime<Utc>| instant.with_timezone(tz).time();
+
+        let totals = self.daily_totals(timespan, tz)?;
+        let days: BTreeSet<_> = totals
+            .iter()
+            .filter(|(_, total)| !total.is_zero())
+            .map(|(day, _)| *day)
+            .collect();
+        let (current_streak, longest_streak) = streaks(&days, today);
+        let mut weekdays: HashMap<Weekday, Duration> = HashMap::new();
+        for (day, total) in &totals {
+            *weekdays.entry(day.weekday()).or_default() += *total;
+        }
+
+        Ok(Statistics {
+            sessions: records.len(),
+            total,
+            mean: total / durations.len() as u32,
+            median: median(&durations),
+            longest: records.iter().max_by_key(|rec| rec.duration()).cloned(),
+            average_start: average_time_of_day(
+                records.iter().map(|rec| local(rec.start.datetime())),
+            ),
+            average_stop: average_time_of_day(records.iter().map(|rec| local(rec.end.datetime()))),
+            days_worked: days.len(),
+            current_streak,
+            longest_streak,
+            busiest_weekday: weekdays.into_iter().max_by_key(|(weekday, total)| {
+                (*total, std::cmp::Reverse(weekday.num_days_from_monday()))
+            }),
+        })
+    }
+
+    /// Expected and tracked time for every day from `from` through `to` in `tz`.
+    ///
+    /// Holidays and the tracker's days off expect no work; time tracked on them still counts.
+    /// Records that run past midnight count towards each day by the time worked on it.
+    fn daily_balance(
+        &self,
+        schedule: &Schedule,
+        holidays: &BTreeSet<NaiveDate>,
+        from: NaiveDate,
+        to: NaiveDate,
+        tz: &chrono_tz::Tz,
+    ) -> Result<Vec<DayBalance>, ReporterError> {
+        let days_off = self
+            .days_off()
+            .change_context(ReporterError)
+            .attach("failed to query days off")?;
+        let (first, end) = (start_of_day(from, tz), start_of_day(to + Days::new(1), tz));
+        // records that started earlier may still run into the first day
+        let timespan = ReportTimespan::Range {
+            from: DateTime::<Utc>::MIN_UTC,
+            to: end,
+        };
+        let mut totals: BTreeMap<NaiveDate, Duration> = BTreeMap::new();
+        for record in self.records_in(timespan)? {
+            if record.end.datetime() <= first {
+                continue;
+            }
+            for (date, worked) in split_at_midnight(&record, tz) {
+                *totals.entry(date).or_default() += worked;
+            }
+        }
+        let balance = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| DayBalance {
+                date,
+                expected: match holidays.contains(&date) || days_off.contains(&date) {
+                    true => Duration::ZERO,
+                    false => schedule.expected_on(date),
+                },
+                actual: totals.get(&date).copied().unwrap_or_default(),
+            })
+            .collect();
+        Ok(balance)
+    }
+
+    /// Compares the time tracked in the current day or week with each goal.
+    ///
+    /// Goals that do not apply on the day of `now` in `tz` are left out. Like the other
+    /// aggregates, records and running sessions count towards the period they started in.
+    fn goal_progress(
+        &self,
+        goals: &[Goal],
+        now: DateTime<Utc>,
+        tz: &chrono_tz::Tz,
+    ) -> Result<Vec<GoalProgress>, ReporterError> {
+        let today = now.with_timezone(tz).date_naive();
+        let sessions = self
+            .active_sessions()
+            .change_context(ReporterError)
+            .attach("failed to query running sessions")?;
+        let mut progress = Vec::new();
+        for goal in goals.iter().filter(|goal| goal.applies_on(today)) {
+            let (from, to) = goal.period.bounds(today, tz);
+            let timespan = ReportTimespan::Range { from, to };
+            let recorded: Duration = self
+                .records_in(timespan)?
+                .iter()
+                .filter(|rec| goal.counts(rec.project.as_deref()))
+                .map(TimeRecord::duration)
+                .sum();
+            let active: Vec<_> = sessions
+                .iter()
+                .filter(|session| timespan.contains(&session.start))
+                .filter(|session| goal.counts(session.details.project.as_deref()))
+                .collect();
+            progress.push(GoalProgress {
+                goal: goal.clone(),
+                from,
+                to,
+                done: recorded + active.iter().map(|session| session.elapsed(now)).sum(),
+                running: active.iter().any(|session| !session.is_paused()),
+            });
+        }
+        Ok(progress)
     }
 }
 
@@ -42,14 +663,47 @@ pub trait Reporter: Tracker {
 mod tests {
     use std::time::Duration;
 
-    use assert_cmd::assert;
     use ttlib::FakeTracker;
 
     use super::*;
+    use crate::feature::tracker::{PauseStatus, ResumeStatus, SessionDetails};
+
+    #[test]
+    fn preceding_period_has_the_same_length() {
+        let at = |day| Utc.with_ymd_and_hms(2026, 10, day, 0, 0, 0).unwrap();
+        let week = ReportTimespan::Range {
+            from: at(19),
+            to: at(26),
+        };
+
+        assert_eq!(
+            week.preceding(),
+            Some(ReportTimespan::Range {
+                from: at(12),
+                to: at(19)
+            })
+        );
+        assert_eq!(ReportTimespan::All.preceding(), None);
+        let open = ReportTimespan::Range {
+            from: at(19),
+            to: DateTime::<Utc>::MAX_UTC,
+        };
+        assert_eq!(open.preceding(), None);
+    }
+
+    #[test]
+    fn month_bounds_follow_the_calendar() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 14).unwrap();
+
+        let (from, to) = Period::Month.bounds(date, &chrono_tz::UTC);
+
+        assert_eq!(from, Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(to, Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap());
+    }
 
     #[test]
     fn calculate_correct_duration_when_there_are_no_records() {
-        let mut tracker = FakeTracker::default();
+        let tracker = FakeTracker::default();
 
         let duration = tracker
             .total_duration(ReportTimespan::Last(Duration::from_secs(1)))
@@ -76,46 +730,474 @@ mod tests {
 
         assert!(duration >= Duration::from_millis(20));
     }
+
+    #[test]
+    fn breaks_do_not_count_towards_the_total() {
+        let mut tracker = FakeTracker::default();
+
+        tracker.start().unwrap();
+        assert_eq!(tracker.pause().unwrap(), PauseStatus::Paused);
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(tracker.resume().unwrap(), ResumeStatus::Resumed);
+        assert_eq!(tracker.resume().unwrap(), ResumeStatus::NotPaused);
+        tracker.stop().unwrap();
+
+        let record = tracker.records().unwrap().next().unwrap();
+        assert_eq!(record.breaks.len(), 1);
+        let total = tracker.total_duration(ReportTimespan::All).unwrap();
+        assert_eq!(total, record.duration());
+        assert!(total + Duration::from_millis(50) <= record.gross_duration());
+    }
+
+    #[test]
+    fn daily_totals_sum_records_by_start_day() {
+        let mut tracker = FakeTracker::default();
+        let record = |day: u32, hour: u32, minutes: i64| {
+            let start = Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+            let end = start + chrono::Duration::minutes(minutes);
+            TimeRecord::new(start.into(), end.into(), Default::default())
+        };
+        tracker
+            .add_records(vec![
+                record(18, 9, 60),
+                record(18, 13, 30),
+                record(19, 23, 120),
+            ])
+            .unwrap();
+
+        let totals = tracker.daily_totals(ReportTimespan::All, &Utc).unwrap();
+
+        let day = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[&day(18)], Duration::from_secs(90 * 60));
+        assert_eq!(totals[&day(19)], Duration::from_secs(120 * 60));
+    }
+
+    #[test]
+    fn project_totals_sum_records_by_project() {
+        let mut tracker = FakeTracker::default();
+        let record = |hour: u32, project: Option<&str>| {
+            let start = Utc.with_ymd_and_hms(2026, 10, 19, hour, 0, 0).unwrap();
+            let end = start + chrono::Duration::minutes(30);
+            let details = SessionDetails {
+                project: project.map(str::to_string),
+                ..Default::default()
+            };
+            TimeRecord::new(start.into(), end.into(), details)
+        };
+        tracker
+            .add_records(vec![
+                record(9, Some("web")),
+                record(10, None),
+                record(11, Some("web")),
+            ])
+            .unwrap();
+
+        let totals = tracker.project_totals(ReportTimespan::All).unwrap();
+
+        assert_eq!(
+            totals,
+            BTreeMap::from([
+                (None, Duration::from_secs(30 * 60)),
+                (Some("web".to_string()), Duration::from_secs(60 * 60)),
+            ])
+        );
+    }
+
+    #[test]
+    fn billing_splits_time_by_rate_and_billable_flag() {
+        let mut tracker = FakeTracker::default();
+        let start = Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap();
+        let record = |tags: &[&str], hours: i64, non_billable: bool| {
+            let details = SessionDetails {
+                project: Some("website".to_string()),
+                tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                non_billable,
+                ..Default::default()
+            };
+            let end = start + chrono::Duration::hours(hours);
+            TimeRecord::new(start.into(), end.into(), details)
+        };
+        tracker
+            .add_records(vec![
+                record(&[], 2, false),
+                record(&["urgent"], 1, false),
+                record(&[], 1, true),
+            ])
+            .unwrap();
+        let rates: RateTable = serde_json::from_str(
+            r#"{"clients":{"acme":{"rate":100,"currency":"EUR","projects":["website"]}},"tags":{"urgent":150}}"#,
+        )
+        .unwrap();
+
+        let lines = tracker.billing(ReportTimespan::All, &rates).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].rate, Some(Money(10000)));
+        assert_eq!(lines[0].billable, Duration::from_secs(2 * 3600));
+        assert_eq!(lines[0].non_billable, Duration::from_secs(3600));
+        assert_eq!(lines[0].amount(), Some(Money(20000)));
+        assert_eq!(lines[1].amount(), Some(Money(15000)));
+        assert_eq!(lines[1].client.as_deref(), Some("acme"));
+    }
+
+    #[test]
+    fn statistics_cover_sessions_days_and_streaks() {
+        let mut tracker = FakeTracker::default();
+        let record = |day: u32, hour: u32, minutes: i64| {
+            let start = Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+            let end = start + chrono::Duration::minutes(minutes);
+            TimeRecord::new(start.into(), end.into(), Default::default())
+        };
+        tracker
+            .add_records(vec![
+                record(12, 9, 60),
+                record(13, 9, 240),
+                record(13, 14, 60),
+                record(14, 10, 120),
+                record(17, 11, 30),
+                record(18, 9, 90),
+            ])
+            .unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+
+        let stats = tracker
+            .statistics(ReportTimespan::All, today, &Utc)
+            .unwrap();
+
+        assert_eq!(stats.sessions, 6);
+        assert_eq!(stats.total, Duration::from_secs(600 * 60));
+        assert_eq!(stats.mean, Duration::from_secs(100 * 60));
+        assert_eq!(stats.median, Duration::from_secs(75 * 60));
+        assert_eq!(
+            stats.longest.unwrap().duration(),
+            Duration::from_secs(240 * 60)
+        );
+        assert_eq!(stats.average_start, NaiveTime::from_hms_opt(10, 14, 52));
+        assert_eq!(stats.days_worked, 5);
+        assert_eq!((stats.current_streak, stats.longest_streak), (2, 3));
+        assert_eq!(
+            stats.busiest_weekday,
+            Some((Weekday::Tue, Duration::from_secs(300 * 60)))
+        );
+    }
+
+    #[test]
+    fn average_times_of_day_wrap_around_midnight() {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+
+        assert_eq!(
+            average_time_of_day([time(23), time(1)].into_iter()),
+            Some(time(0))
+        );
+        assert_eq!(
+            average_time_of_day([time(22), time(23), time(1), time(2)].into_iter()),
+            Some(time(0))
+        );
+        assert_eq!(average_time_of_day([time(6), time(18)].into_iter()), None);
+        assert_eq!(average_time_of_day(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn statistics_of_an_empty_window_are_zero() {
+        let tracker = FakeTracker::default();
+        let today = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+
+        let stats = tracker
+            .statistics(ReportTimespan::All, today, &Utc)
+            .unwrap();
+
+        assert_eq!(stats, Statistics::default());
+    }
+
+    #[test]
+    fn budget_usage_sums_budgeted_projects_per_week() {
+        let mut tracker = FakeTracker::default();
+        let record = |day: u32, project: &str| {
+            let start = Utc.with_ymd_and_hms(2026, 10, day, 9, 0, 0).unwrap();
+            let end = start + chrono::Duration::hours(3);
+            let details = SessionDetails {
+                project: Some(project.to_string()),
+                ..Default::default()
+            };
+            TimeRecord::new(start.into(), end.into(), details)
+        };
+        tracker
+            .add_records(vec![
+                record(9, "web"),
+                record(12, "web"),
+                record(13, "web"),
+                record(13, "api"),
+            ])
+            .unwrap();
+        let budget = Budget {
+            limit: Duration::from_secs(10 * 3600),
+            alerts: vec![80, 100],
+        };
+        let budgets = BTreeMap::from([("web".to_string(), budget)]);
+
+        let usage = tracker.budget_usage(&budgets, &Utc).unwrap();
+
+        assert_eq!(usage.len(), 1);
+        let monday = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        assert_eq!(
+            usage[0].weeks,
+            BTreeMap::from([
+                (monday(5), Duration::from_secs(3 * 3600)),
+                (monday(12), Duration::from_secs(6 * 3600)),
+            ])
+        );
+        assert_eq!(usage[0].remaining(), Duration::from_secs(3600));
+        assert_eq!(usage[0].alert(), Some(80));
+    }
+
+    #[test]
+    fn daily_balance_skips_holidays_and_days_off() {
+        let mut tracker = FakeTracker::default();
+        let day = |day| NaiveDate::from_ymd_opt(2026, 12, day).unwrap();
+        let record = |date: NaiveDate, hours: i64| {
+            let start = start_of_day(date, &chrono_tz::UTC) + chrono::Duration::hours(9);
+            let end = start + chrono::Duration::hours(hours);
+            TimeRecord::new(start.into(), end.into(), Default::default())
+        };
+        tracker
+            .add_records(vec![
+                record(day(21), 9),
+                record(day(22), 7),
+                record(day(26), 2),
+            ])
+            .unwrap();
+        tracker.set_day_off(day(24), true).unwrap();
+        let schedule = Schedule {
+            since: day(21),
+            hours: [
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ]
+            .into_iter()
+            .map(|weekday| (weekday, Duration::from_secs(8 * 3600)))
+            .collect(),
+            holidays: None,
+        };
+        let holidays = BTreeSet::from([day(25)]);
+
+        let balance = tracker
+            .daily_balance(&schedule, &holidays, day(21), day(27), &chrono_tz::UTC)
+            .unwrap();
+
+        let differences: Vec<_> = balance
+            .iter()
+            .map(|day| day.difference().num_hours())
+            .collect();
+        // Mon +1, Tue -1, Wed -8, Thu off, Fri holiday, Sat +2, Sun nothing
+        assert_eq!(differences, vec![1, -1, -8, 0, 0, 2, 0]);
+    }
+
+    #[test]
+    fn daily_balance_splits_sessions_at_local_midnight() {
+        let mut tracker = FakeTracker::default();
+        let tz = chrono_tz::Europe::Berlin;
+        let day = |day| NaiveDate::from_ymd_opt(2026, 12, day).unwrap();
+        let at = |date: NaiveDate, hour: i64, minute: i64| {
+            start_of_day(date, &tz) + chrono::Duration::minutes(hour * 60 + minute)
+        };
+        let record = |start: DateTime<Utc>, end: DateTime<Utc>| {
+            TimeRecord::new(start.into(), end.into(), Default::default())
+        };
+        let late = record(at(day(21), 22, 0), at(day(22), 2, 0)).with_breaks(vec![Break {
+            start: at(day(21), 23, 30),
+            end: at(day(22), 0, 30),
+        }]);
+        tracker
+            .add_records(vec![record(at(day(20), 23, 0), at(day(21), 1, 0)), late])
+            .unwrap();
+        let schedule = Schedule {
+            since: day(21),
+            hours: HashMap::new(),
+            holidays: None,
+        };
+
+        let balance = tracker
+            .daily_balance(&schedule, &BTreeSet::new(), day(21), day(22), &tz)
+            .unwrap();
+
+        let actual: Vec<_> = balance
+            .iter()
+            .map(|day| day.actual.as_secs() / 60)
+            .collect();
+        // 00:00-01:00 and 22:00-23:30 on the 21st, 00:30-02:00 on the 22nd
+        assert_eq!(actual, vec![150, 90]);
+    }
+
+    #[test]
+    fn goal_progress_counts_the_current_period_and_project() {
+        let mut tracker = FakeTracker::default();
+        let record = |day: u32, hour: u32, project: &str| {
+            let start = Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+            let end = start + chrono::Duration::hours(2);
+            let details = SessionDetails {
+                project: Some(project.to_string()),
+                ..Default::default()
+            };
+            TimeRecord::new(start.into(), end.into(), details)
+        };
+        tracker
+            .add_records(vec![
+                // the week before
+                record(18, 9, "acme"),
+                record(19, 9, "acme"),
+                record(20, 9, "beta"),
+                record(21, 9, "acme"),
+            ])
+            .unwrap();
+        let goal = |period, hours: u64, project: Option<&str>, days| Goal {
+            period,
+            target: Duration::from_secs(hours * 3600),
+            project: project.map(str::to_string),
+            days,
+        };
+        let goals = [
+            goal(Period::Day, 8, None, None),
+            goal(Period::Week, 3, Some("acme"), None),
+            goal(Period::Day, 8, None, Some(vec![Weekday::Sat, Weekday::Sun])),
+        ];
+        let now = Utc.with_ymd_and_hms(2026, 10, 21, 12, 0, 0).unwrap();
+
+        let progress = tracker.goal_progress(&goals, now, &chrono_tz::UTC).unwrap();
+
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[0].done, Duration::from_secs(2 * 3600));
+        assert_eq!(progress[0].remaining(), Duration::from_secs(6 * 3600));
+        assert_eq!(progress[1].done, Duration::from_secs(4 * 3600));
+        assert!(progress[1].remaining().is_zero());
+        assert!(!progress[1].running);
+    }
 }
 
 #[cfg(test)]
 mod ttlib {
     use crate::feature::tracker::{
-        EndTime, StartTime, StartupStatus, TimeRecord, Tracker, TrackerError,
+        ActiveSession, Break, EndTime, PauseStatus, RecordId, ResumeStatus, SessionDetails,
+        StartTime, StartupStatus, Task, TimeRecord, Tracker, TrackerError, DEFAULT_TIMER,
     };
 
     use super::*;
     #[derive(Debug, Default)]
     pub struct FakeTracker {
-        tracking: Option<StartTime>,
+        tracking: Option<ActiveSession>,
         records: Vec<TimeRecord>,
+        days_off: BTreeSet<NaiveDate>,
+        last_invoice: u32,
+        tasks: Vec<Task>,
     }
     impl Tracker for FakeTracker {
-        fn start(&mut self) -> Result<StartupStatus, TrackerError> {
+        fn start_with(&mut self, details: SessionDetails) -> Result<StartupStatus, TrackerError> {
             if self.tracking.is_some() {
-                return Ok(StartupStatus::Running);
+                return Ok(StartupStatus::Running(DEFAULT_TIMER.to_string()));
             }
-            self.tracking = Some(StartTime::now());
+            self.tracking = Some(ActiveSession {
+                timer: DEFAULT_TIMER.to_string(),
+                start: StartTime::now(),
+                details,
+                breaks: Vec::new(),
+                paused_at: None,
+            });
             Ok(StartupStatus::Started)
         }
 
-        fn is_running(&self) -> bool {
-            self.tracking.is_some()
+        fn is_running(&self) -> Result<bool, TrackerError> {
+            Ok(self.tracking.is_some())
+        }
+
+        fn active_sessions(&self) -> Result<Vec<ActiveSession>, TrackerError> {
+            Ok(self.tracking.iter().cloned().collect())
         }
 
         fn stop(&mut self) -> Result<(), TrackerError> {
-            let start_time = self.tracking.take().unwrap();
-            let end_time = EndTime::now();
-            let record = TimeRecord {
-                start: start_time,
-                end: end_time,
+            let session = self.tracking.take().unwrap();
+            self.records.push(session.finish(EndTime::now()));
+            Ok(())
+        }
+
+        fn pause(&mut self) -> Result<PauseStatus, TrackerError> {
+            let session = self.tracking.as_mut().unwrap();
+            if session.paused_at.is_some() {
+                return Ok(PauseStatus::AlreadyPaused);
+            }
+            session.paused_at = Some(Utc::now());
+            Ok(PauseStatus::Paused)
+        }
+
+        fn resume(&mut self) -> Result<ResumeStatus, TrackerError> {
+            let session = self.tracking.as_mut().unwrap();
+            let Some(start) = session.paused_at.take() else {
+                return Ok(ResumeStatus::NotPaused);
             };
-            self.records.push(record);
+            session.breaks.push(Break {
+                start,
+                end: Utc::now(),
+            });
+            Ok(ResumeStatus::Resumed)
+        }
+
+        fn add_note(&mut self, note: &str) -> Result<(), TrackerError> {
+            let session = self.tracking.as_mut().unwrap();
+            session.details.append_note(note);
             Ok(())
         }
 
         fn records(&self) -> Result<impl Iterator<Item = TimeRecord>, TrackerError> {
-            Ok(self.records.iter().copied())
+            Ok(self.records.iter().cloned())
+        }
+
+        fn add_records(&mut self, records: Vec<TimeRecord>) -> Result<(), TrackerError> {
+            self.records.extend(records);
+            Ok(())
+        }
+
+        fn update_record(&mut self, record: TimeRecord) -> Result<(), TrackerError> {
+            for stored in self.records.iter_mut().filter(|rec| rec.id == record.id) {
+                *stored = record.clone();
+            }
+            Ok(())
+        }
+
+        fn remove_record(&mut self, id: RecordId) -> Result<(), TrackerError> {
+            self.records.retain(|rec| rec.id != id);
+            Ok(())
+        }
+
+        fn days_off(&self) -> Result<BTreeSet<NaiveDate>, TrackerError> {
+            Ok(self.days_off.clone())
+        }
+
+        fn set_day_off(&mut self, date: NaiveDate, off: bool) -> Result<(), TrackerError> {
+            match off {
+                true => self.days_off.insert(date),
+                false => self.days_off.remove(&date),
+            };
+            Ok(())
+        }
+
+        fn issue_invoice(&mut self, ids: &[RecordId]) -> Result<u32, TrackerError> {
+            self.last_invoice += 1;
+            for record in self.records.iter_mut().filter(|rec| ids.contains(&rec.id)) {
+                record.invoice = Some(self.last_invoice);
+            }
+            Ok(self.last_invoice)
+        }
+
+        fn tasks(&self) -> Result<Vec<Task>, TrackerError> {
+            Ok(self.tasks.clone())
+        }
+
+        fn save_task(&mut self, task: Task) -> Result<(), TrackerError> {
+            self.tasks.retain(|stored| stored.name != task.name);
+            self.tasks.push(task);
+            Ok(())
         }
     }
 
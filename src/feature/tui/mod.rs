@@ -0,0 +1,482 @@
+use std::{
+    io::{self, IsTerminal},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Datelike, Days, Utc};
+use chrono_tz::Tz;
+use error_stack::{Report, Result, ResultExt};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    DefaultTerminal,
+};
+
+use super::tracker::{
+    reporter::{ReportTimespan, Reporter},
+    ActiveSession, SessionDetails, TimeRecord,
+};
+use crate::common::start_of_day;
+
+mod ui;
+
+const TICK: Duration = Duration::from_millis(250);
+// picks up changes made by other `track` processes
+const RELOAD_EVERY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, thiserror::Error)]
+#[error("tui error")]
+pub struct TuiError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Project,
+    Tags,
+    Note,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    /// editing a field of the selected record
+    Edit {
+        field: Field,
+        input: String,
+    },
+    /// project for the session started by a switch
+    Switch {
+        input: String,
+    },
+    ConfirmDelete,
+}
+
+/// Dashboard state; all data goes through the `Tracker` and `Reporter` traits.
+pub struct App<T> {
+    tracker: T,
+    tz: Tz,
+    records: Vec<TimeRecord>,
+    sessions: Vec<ActiveSession>,
+    /// start of today and of this week
+    today_from: DateTime<Utc>,
+    week_from: DateTime<Utc>,
+    today: Duration,
+    week: Duration,
+    selected: usize,
+    mode: Mode,
+    message: Option<String>,
+    quit: bool,
+}
+
+impl<T: Reporter> App<T> {
+    pub fn new(tracker: T, tz: Tz) -> Self {
+        Self {
+            tracker,
+            tz,
+            records: Vec::new(),
+            sessions: Vec::new(),
+            today_from: DateTime::<Utc>::MIN_UTC,
+            week_from: DateTime::<Utc>::MIN_UTC,
+            today: Duration::ZERO,
+            week: Duration::ZERO,
+            selected: 0,
+            mode: Mode::Normal,
+            message: None,
+            quit: false,
+        }
+    }
+
+    /// Reloads records, running sessions and totals.
+    pub fn refresh(&mut self) -> Result<(), TuiError> {
+        let today = Utc::now().with_timezone(&self.tz).date_naive();
+        let monday = today - Days::new(today.weekday().num_days_from_monday().into());
+        self.today_from = start_of_day(today, &self.tz);
+        self.week_from = start_of_day(monday, &self.tz);
+        let since = |from| ReportTimespan::Range {
+            from,
+            to: DateTime::<Utc>::MAX_UTC,
+        };
+        self.today = self
+            .tracker
+            .total_duration(since(self.today_from))
+            .change_context(TuiError)
+            .attach_printable("failed to total today's records")?;
+        self.week = self
+            .tracker
+            .total_duration(since(self.week_from))
+            .change_context(TuiError)
+            .attach_printable("failed to total this week's records")?;
+
+        let mut records = self
+            .tracker
+            .records_in(ReportTimespan::All)
+            .change_context(TuiError)
+            .attach_printable("failed to load records")?;
+        // newest first, keeping stored order for records started in the same millisecond
+        records.sort_by_key(|rec| rec.start.timestamp_millis());
+        records.reverse();
+        self.records = records;
+        self.selected = self.selected.min(self.records.len().saturating_sub(1));
+
+        self.sessions = self
+            .tracker
+            .active_sessions()
+            .change_context(TuiError)
+            .attach_printable("failed to read running timers")?;
+        Ok(())
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        self.message = None;
+        let result = match self.mode.clone() {
+            Mode::Normal => self.normal_key(key.code),
+            Mode::ConfirmDelete => self.confirm_delete_key(key.code),
+            Mode::Edit { field, input } => {
+                self.input_key(key.code, input, |app, input| app.save_field(field, input))
+            }
+            Mode::Switch { input } => {
+                self.input_key(key.code, input, |app, input| app.switch_to(input))
+            }
+        };
+        if let Err(report) = result {
+            let reason = report
+                .frames()
+                .find_map(|frame| frame.downcast_ref::<&str>())
+                .copied()
+                .unwrap_or("unknown error");
+            self.message = Some(format!("error: {reason}"));
+        }
+    }
+
+    fn normal_key(&mut self, code: KeyCode) -> Result<(), TuiError> {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.records.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => {
+                self.selected = self.records.len().saturating_sub(1)
+            }
+            KeyCode::Char('r') => self.refresh()?,
+            KeyCode::Char('s') => self.toggle()?,
+            KeyCode::Char('w') => {
+                self.mode = Mode::Switch {
+                    input: String::new(),
+                }
+            }
+            KeyCode::Char('p') => self.begin_edit(Field::Project),
+            KeyCode::Char('t') => self.begin_edit(Field::Tags),
+            KeyCode::Char('n') => self.begin_edit(Field::Note),
+            KeyCode::Char('b') => self.toggle_billable()?,
+            KeyCode::Char('d') if self.selected_record().is_some() => {
+                self.mode = Mode::ConfirmDelete
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn confirm_delete_key(&mut self, code: KeyCode) -> Result<(), TuiError> {
+        self.mode = Mode::Normal;
+        if code != KeyCode::Char('y') {
+            return Ok(());
+        }
+        let Some(record) = self.selected_record() else {
+            return Ok(());
+        };
+        let id = record.id;
+        self.tracker
+            .remove_record(id)
+            .change_context(TuiError)
+            .attach_printable("failed to delete record")?;
+        self.message = Some("record deleted".to_string());
+        self.refresh()
+    }
+
+    fn input_key(
+        &mut self,
+        code: KeyCode,
+        mut input: String,
+        commit: impl FnOnce(&mut Self, String) -> Result<(), TuiError>,
+    ) -> Result<(), TuiError> {
+        match code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                commit(self, input)?;
+            }
+            KeyCode::Backspace | KeyCode::Char(_) => {
+                match code {
+                    KeyCode::Char(ch) => input.push(ch),
+                    _ => {
+                        input.pop();
+                    }
+                }
+                self.mode = match &self.mode {
+                    Mode::Edit { field, .. } => Mode::Edit {
+                        field: *field,
+                        input,
+                    },
+                    _ => Mode::Switch { input },
+                };
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn begin_edit(&mut self, field: Field) {
+        let Some(record) = self.selected_record() else {
+            return;
+        };
+        let input = match field {
+            Field::Project => record.project.clone().unwrap_or_default(),
+            Field::Tags => record.tags.join(", "),
+            Field::Note => record.note.clone().unwrap_or_default(),
+        };
+        self.mode = Mode::Edit { field, input };
+    }
+
+    fn save_field(&mut self, field: Field, input: String) -> Result<(), TuiError> {
+        let Some(mut record) = self.selected_record().cloned() else {
+            return Ok(());
+        };
+        let text = input.trim();
+        let text = (!text.is_empty()).then(|| text.to_string());
+        match field {
+            Field::Project => record.project = text,
+            Field::Note => record.note = text,
+            Field::Tags => {
+                record.tags = input
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            }
+        }
+        self.tracker
+            .update_record(record)
+            .change_context(TuiError)
+            .attach_printable("failed to save record")?;
+        self.refresh()
+    }
+
+    fn toggle_billable(&mut self) -> Result<(), TuiError> {
+        let Some(mut record) = self.selected_record().cloned() else {
+            return Ok(());
+        };
+        record.non_billable = !record.non_billable;
+        self.tracker
+            .update_record(record)
+            .change_context(TuiError)
+            .attach_printable("failed to save record")?;
+        self.refresh()
+    }
+
+    fn toggle(&mut self) -> Result<(), TuiError> {
+        if self.tracker.is_running().change_context(TuiError)? {
+            self.tracker
+                .stop()
+                .change_context(TuiError)
+                .attach_printable("failed to stop tracking")?;
+            self.message = Some("stopped".to_string());
+        } else {
+            self.tracker
+                .start()
+                .change_context(TuiError)
+                .attach_printable("failed to start tracking")?;
+            self.message = Some("started".to_string());
+        }
+        self.refresh()
+    }
+
+    /// Stops the running session, if any, and starts one for `project`.
+    fn switch_to(&mut self, project: String) -> Result<(), TuiError> {
+        let project = project.trim();
+        let details = SessionDetails {
+            project: (!project.is_empty()).then(|| project.to_string()),
+            ..Default::default()
+        };
+        self.tracker
+            .switch(details)
+            .change_context(TuiError)
+            .attach_printable("failed to switch tracking")?;
+        self.message = Some(format!("switched to {}", project));
+        self.refresh()
+    }
+
+    fn selected_record(&self) -> Option<&TimeRecord> {
+        self.records.get(self.selected)
+    }
+
+    /// Time worked by the running sessions since `from`.
+    fn running_since(&self, from: DateTime<Utc>) -> Duration {
+        let now = Utc::now();
+        self.sessions
+            .iter()
+            .map(|session| session.elapsed_since(from, now))
+            .sum()
+    }
+}
+
+/// Runs the dashboard until the user quits.
+pub fn run<T: Reporter>(tracker: T, tz: Tz) -> Result<(), TuiError> {
+    if !io::stdout().is_terminal() {
+        return Err(Report::new(TuiError).attach_printable("the dashboard needs a terminal"));
+    }
+    let mut app = App::new(tracker, tz);
+    app.refresh()?;
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop<T: Reporter>(
+    terminal: &mut DefaultTerminal,
+    app: &mut App<T>,
+) -> Result<(), TuiError> {
+    let mut reloaded = Instant::now();
+    while !app.quit {
+        terminal
+            .draw(|frame| ui::draw(frame, app))
+            .change_context(TuiError)
+            .attach_printable("failed to draw the dashboard")?;
+        let ready = event::poll(TICK)
+            .change_context(TuiError)
+            .attach_printable("failed to poll terminal events")?;
+        if ready {
+            let event = event::read()
+                .change_context(TuiError)
+                .attach_printable("failed to read terminal event")?;
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key);
+                }
+            }
+        }
+        if app.mode == Mode::Normal && reloaded.elapsed() >= RELOAD_EVERY {
+            app.refresh()?;
+            reloaded = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::{fixture::PathChild, TempDir};
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::feature::tracker::{flatfile::FlatFileTracker, Break, Tracker};
+
+    fn app() -> (TempDir, App<FlatFileTracker>) {
+        let temp = TempDir::new().unwrap();
+        let tracker =
+            FlatFileTracker::new(temp.child("db.json").path(), temp.child("lockfile").path());
+        let mut app = App::new(tracker, Tz::UTC);
+        app.refresh().unwrap();
+        (temp, app)
+    }
+
+    fn press(app: &mut App<FlatFileTracker>, keys: &str) {
+        for ch in keys.chars() {
+            let code = match ch {
+                '\n' => KeyCode::Enter,
+                '\x1b' => KeyCode::Esc,
+                ch => KeyCode::Char(ch),
+            };
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn start_stop_and_switch_keys_drive_the_tracker() {
+        let (_temp, mut app) = app();
+
+        press(&mut app, "s");
+        assert!(app.tracker.is_running().unwrap());
+        press(&mut app, "wacme\n");
+        assert_eq!(app.sessions[0].details.project.as_deref(), Some("acme"));
+        assert_eq!(app.records.len(), 1);
+        press(&mut app, "s");
+
+        assert!(!app.tracker.is_running().unwrap());
+        assert_eq!(app.records.len(), 2);
+        assert_eq!(app.records[0].project.as_deref(), Some("acme"));
+    }
+
+    #[test]
+    fn edits_fields_of_the_selected_record() {
+        let (_temp, mut app) = app();
+        press(&mut app, "ss");
+
+        press(&mut app, "pacme\n");
+        press(&mut app, "tbillable, review\n");
+        press(&mut app, "nnot saved\x1b");
+        press(&mut app, "b");
+
+        let record = &app.records[0];
+        assert_eq!(record.project.as_deref(), Some("acme"));
+        assert_eq!(record.tags, vec!["billable", "review"]);
+        assert_eq!(record.note, None);
+        assert!(!record.is_billable());
+    }
+
+    #[test]
+    fn deleting_asks_for_confirmation() {
+        let (_temp, mut app) = app();
+        press(&mut app, "ss");
+
+        press(&mut app, "dn");
+        assert_eq!(app.records.len(), 1);
+        press(&mut app, "dy");
+        assert!(app.records.is_empty());
+    }
+
+    #[test]
+    fn totals_count_running_sessions_only_within_the_period() {
+        let (_temp, mut app) = app();
+        let now = Utc::now();
+        let minutes = |minutes| chrono::Duration::minutes(minutes);
+        app.sessions = vec![ActiveSession {
+            timer: "default".to_string(),
+            start: (now - minutes(180)).into(),
+            details: SessionDetails::default(),
+            // ten minutes of it fall into the last hour
+            breaks: vec![Break {
+                start: now - minutes(70),
+                end: now - minutes(50),
+            }],
+            paused_at: None,
+        }];
+
+        let running = app.running_since(now - minutes(60));
+
+        assert!(running >= Duration::from_secs(50 * 60));
+        assert!(running < Duration::from_secs(51 * 60));
+    }
+
+    #[test]
+    fn draws_totals_and_records() {
+        let (_temp, mut app) = app();
+        press(&mut app, "ss");
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 20)).unwrap();
+
+        terminal.draw(|frame| ui::draw(frame, &app)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Today 00:00:00"));
+        assert!(screen.contains("idle"));
+        assert!(screen.contains("00:00:00"));
+    }
+}
@@ -0,0 +1,94 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::{App, Field, Mode};
+use crate::feature::{
+    report_fmt::{log_line, DurationFormat, HMSFormatter},
+    tracker::reporter::Reporter,
+};
+
+const HELP: &str =
+    "s start/stop  w switch  p project  t tags  n note  b billable  d delete  r reload  q quit";
+
+pub fn draw<T: Reporter>(frame: &mut Frame, app: &App<T>) {
+    let [status, records, footer] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(status_panel(app), status);
+
+    let items: Vec<_> = app
+        .records
+        .iter()
+        .map(|record| ListItem::new(log_line(record, &app.tz)))
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Records"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(list, records, &mut state);
+
+    frame.render_widget(Paragraph::new(footer_line(app)), footer);
+}
+
+fn status_panel<T: Reporter>(app: &App<T>) -> Paragraph<'static> {
+    let now = chrono::Utc::now();
+    let session = match app.sessions.as_slice() {
+        [] => Line::styled("○ idle", Style::default().fg(Color::DarkGray)),
+        sessions => {
+            let parts: Vec<_> = sessions
+                .iter()
+                .map(|session| {
+                    let project = session.details.project.as_deref().unwrap_or("-");
+                    let paused = if session.is_paused() { " paused" } else { "" };
+                    format!(
+                        "{} {project} {}{paused}",
+                        session.timer,
+                        HMSFormatter.format(session.elapsed(now))
+                    )
+                })
+                .collect();
+            Line::styled(
+                format!("● {}", parts.join("  |  ")),
+                Style::default().fg(Color::Green),
+            )
+        }
+    };
+    let totals = Line::from(format!(
+        "Today {}   Week {}",
+        HMSFormatter.format(app.today + app.running_since(app.today_from)),
+        HMSFormatter.format(app.week + app.running_since(app.week_from))
+    ));
+    Paragraph::new(vec![session, totals])
+        .block(Block::default().borders(Borders::ALL).title("track"))
+}
+
+fn footer_line<T: Reporter>(app: &App<T>) -> Line<'static> {
+    match &app.mode {
+        Mode::Normal => match &app.message {
+            Some(message) => Line::from(message.clone()),
+            None => Line::styled(HELP, Style::default().fg(Color::DarkGray)),
+        },
+        Mode::Edit { field, input } => {
+            let label = match field {
+                Field::Project => "project",
+                Field::Tags => "tags (comma separated)",
+                Field::Note => "note",
+            };
+            Line::from(format!("{label}: {input}█"))
+        }
+        Mode::Switch { input } => Line::from(format!("switch to project: {input}█")),
+        Mode::ConfirmDelete => Line::styled(
+            "delete the selected record? (y/n)",
+            Style::default().fg(Color::Red),
+        ),
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use assert_cmd::Command;
 
@@ -19,6 +19,18 @@ fn tracking_paths() -> (TempDir, PathBuf, PathBuf) {
     (temp, lockfile, db)
 }
 
+/// `track` on the given database, lockfile and config.
+fn track(db: &Path, lockfile: &Path, config: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("track").expect("track binary is built");
+    cmd.arg("--db-dir")
+        .arg(db)
+        .arg("--lockfile")
+        .arg(lockfile)
+        .arg("--config")
+        .arg(config);
+    cmd
+}
+
 #[test]
 fn start_command_tracking_time() -> TestResult {
     let (tempdir, lockfile, db) = tracking_paths();
@@ -28,7 +40,7 @@ fn start_command_tracking_time() -> TestResult {
 
     Command::cargo_bin("track")?
         .arg("--db-dir")
-        .arg("db.json")
+        .arg(&db)
         .arg("--lockfile")
         .arg(&lockfile)
         .arg("start")
@@ -49,9 +61,9 @@ fn stop_command_stops_tracking_time() -> TestResult {
     assert!(!lockfile.exists(), "Lockfile should not exist yet.");
     assert!(!db.exists(), "Database file should not exist yet.");
 
-    start_tracking(&lockfile)?;
+    start_tracking(&lockfile, &db)?;
 
-    stop_tracking(&lockfile)?;
+    stop_tracking(&lockfile, &db)?;
 
     assert!(!lockfile.exists());
     // assert!(db.exists());
@@ -62,18 +74,18 @@ fn stop_command_stops_tracking_time() -> TestResult {
 #[test]
 
 fn report_command_generates_report() -> TestResult {
-    let (tempdir, lockfile, db) = tracking_paths();
+    let (_tempdir, lockfile, db) = tracking_paths();
 
     assert!(!lockfile.exists(), "Lockfile should not exist yet.");
     assert!(!db.exists(), "Database file should not exist yet.");
 
-    start_tracking(&lockfile)?;
+    start_tracking(&lockfile, &db)?;
 
-    stop_tracking(&lockfile)?;
+    stop_tracking(&lockfile, &db)?;
 
     Command::cargo_bin("track")?
         .arg("--db-dir")
-        .arg("db.json")
+        .arg(&db)
         .arg("--lockfile")
         .arg(&lockfile)
         .arg("report")
@@ -83,10 +95,10 @@ fn report_command_generates_report() -> TestResult {
     Ok(())
 }
 
-fn stop_tracking(lockfile: &PathBuf) -> Result<(), testresult::TestError> {
+fn stop_tracking(lockfile: &PathBuf, db: &PathBuf) -> Result<(), testresult::TestError> {
     Command::cargo_bin("track")?
         .arg("--db-dir")
-        .arg("db.json")
+        .arg(db)
         .arg("--lockfile")
         .arg(lockfile)
         .arg("stop")
@@ -95,14 +107,931 @@ fn stop_tracking(lockfile: &PathBuf) -> Result<(), testresult::TestError> {
     Ok(())
 }
 
-fn start_tracking(lockfile: &PathBuf) -> Result<(), testresult::TestError> {
+fn start_tracking(lockfile: &PathBuf, db: &PathBuf) -> Result<(), testresult::TestError> {
     Command::cargo_bin("track")?
         .arg("--db-dir")
-        .arg("db.json")
+        .arg(db)
+        .arg("--lockfile")
+        .arg(lockfile)
+        .arg("start")
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn export_command_writes_csv() -> TestResult {
+    let (_tempdir, lockfile, db) = tracking_paths();
+
+    Command::cargo_bin("track")?
+        .arg("--db-dir")
+        .arg(&db)
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .args(["start", "--project", "acme", "--tag", "review"])
+        .assert()
+        .success();
+    stop_tracking(&lockfile, &db)?;
+
+    let output = Command::cargo_bin("track")?
+        .arg("--db-dir")
+        .arg(&db)
         .arg("--lockfile")
         .arg(&lockfile)
+        .args(["export", "--format", "csv"])
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let mut lines = stdout.lines();
+
+    assert_eq!(
+        lines.next(),
+        Some("id,start,end,duration_secs,duration_hms,project,tags,note,billable,breaks,task,invoice")
+    );
+    assert!(lines.next().unwrap().contains(",00:00:00,acme,review,"));
+    Ok(())
+}
+
+#[test]
+fn import_command_merges_new_records_once() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let export = tempdir.path().join("watson.json");
+    std::fs::write(
+        &export,
+        r#"[{"id":"a1","project":"acme","start":"2026-10-18T09:00:00+00:00","stop":"2026-10-18T10:30:00+00:00","tags":[]}]"#,
+    )?;
+    let import = |apply: bool| -> Result<String, testresult::TestError> {
+        let mut cmd = Command::cargo_bin("track")?;
+        cmd.arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .args(["import", "--from", "watson"])
+            .arg(&export);
+        if apply {
+            cmd.arg("--apply");
+        }
+        Ok(String::from_utf8(cmd.output()?.stdout)?)
+    };
+
+    assert!(import(false)?.starts_with("1 records read: 1 new, 0 already tracked"));
+    assert!(!db.exists());
+    assert!(import(true)?.contains("Imported 1 records"));
+    assert!(import(true)?.starts_with("1 records read: 0 new, 1 already tracked"));
+    Ok(())
+}
+
+#[test]
+fn notes_are_shown_in_log_and_filter_reports() -> TestResult {
+    let (_tempdir, lockfile, db) = tracking_paths();
+    let track = || -> Result<Command, testresult::TestError> {
+        let mut cmd = Command::cargo_bin("track")?;
+        cmd.arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile);
+        Ok(cmd)
+    };
+
+    track()?.args(["note", "too early"]).assert().failure();
+    track()?
+        .args(["start", "-m", "reviewing PR 42"])
+        .assert()
+        .success();
+    track()?.args(["note", "left comments"]).assert().success();
+    stop_tracking(&lockfile, &db)?;
+    start_tracking(&lockfile, &db)?;
+    stop_tracking(&lockfile, &db)?;
+
+    let log = String::from_utf8(track()?.arg("log").output()?.stdout)?;
+    assert_eq!(log.lines().count(), 2);
+    assert!(log
+        .lines()
+        .next()
+        .unwrap()
+        .ends_with("reviewing PR 42; left comments"));
+
+    track()?
+        .args(["report", "--search", "pr 42"])
+        .assert()
+        .stdout("00:00:00\n  00:00:00  reviewing PR 42; left comments\n")
+        .success();
+    track()?
+        .args(["report", "--search", "standup"])
+        .assert()
+        .stdout("00:00:00\n")
+        .success();
+    Ok(())
+}
+
+#[test]
+fn report_shows_gross_time_when_session_had_breaks() -> TestResult {
+    let (_tempdir, lockfile, db) = tracking_paths();
+    let track = |command: &str| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg(command)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    assert_eq!(track("pause")?, "No session is running\n");
+    track("start")?;
+    assert_eq!(track("pause")?, "Paused tracking\n");
+    assert_eq!(track("resume")?, "Resumed tracking\n");
+    track("stop")?;
+
+    let report = track("report")?;
+    let mut lines = report.lines();
+    assert!(lines.next().is_some());
+    assert!(lines.next().unwrap().starts_with("gross "));
+    Ok(())
+}
+
+#[test]
+fn named_timers_are_listed_by_status() -> TestResult {
+    let (_tempdir, lockfile, db) = tracking_paths();
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    track(&["start", "--timer", "meeting", "-p", "acme"])?;
+    track(&["start", "--timer", "build-wait"])?;
+    assert_eq!(
+        track(&["start", "--timer", "meeting"])?,
+        "Timer 'meeting' already running\n"
+    );
+
+    let status = track(&["status"])?;
+    let lines: Vec<_> = status.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("build-wait  since "));
+    assert!(lines[1].starts_with("meeting  since "));
+    assert!(lines[1].ends_with("  acme"));
+    Command::cargo_bin("track")?
+        .arg("--db-dir")
+        .arg(&db)
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .args(["report", "--timer", "meeting"])
+        .assert()
+        .failure();
+
+    track(&["stop", "--timer", "meeting"])?;
+    track(&["stop", "--timer", "build-wait"])?;
+    assert_eq!(track(&["status"])?, "No session is running\n");
+    Ok(())
+}
+
+#[test]
+fn tui_needs_a_terminal() -> TestResult {
+    let (_tempdir, lockfile, db) = tracking_paths();
+
+    Command::cargo_bin("track")?
+        .arg("--db-dir")
+        .arg(&db)
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .arg("tui")
+        .assert()
+        .failure();
+    Ok(())
+}
+
+#[test]
+fn calendar_command_prints_requested_weeks() -> TestResult {
+    let (_tempdir, lockfile, db) = tracking_paths();
+
+    let output = Command::cargo_bin("track")?
+        .arg("--db-dir")
+        .arg(&db)
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .args(["calendar", "--weeks", "4"])
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    // header, four weeks, blank line and legend
+    assert_eq!(stdout.lines().count(), 7);
+    assert!(stdout.lines().next().unwrap().contains("Mon Tue Wed"));
+    // output is not a terminal
+    assert!(!stdout.contains('\u{1b}'));
+
+    Command::cargo_bin("track")?
+        .arg("--db-dir")
+        .arg(&db)
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .args(["calendar", "--weeks", "0"])
+        .assert()
+        .failure();
+    Ok(())
+}
+
+#[test]
+fn timeline_uses_configured_timezone() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    std::fs::write(&config, r#"{"timezone":"Asia/Tokyo"}"#)?;
+    let export = tempdir.path().join("watson.json");
+    std::fs::write(
+        &export,
+        r#"[{"id":"a1","project":"acme","start":"2026-10-18T00:00:00+00:00","stop":"2026-10-18T01:30:00+00:00","tags":[]}]"#,
+    )?;
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    track(&[
+        "import",
+        "--from",
+        "watson",
+        "--apply",
+        export.to_str().unwrap(),
+    ])?;
+
+    let timeline = track(&["timeline", "--date", "2026-10-18"])?;
+
+    assert!(timeline.starts_with("Sun 2026-10-18\n"));
+    assert!(timeline.contains("acme "));
+    assert!(timeline.contains("01:30:00"));
+    assert!(track(&["timeline", "--date", "2026-10-17"])?.ends_with("no records\n"));
+    Ok(())
+}
+
+#[test]
+fn stats_average_start_times_around_midnight() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    std::fs::write(&config, r#"{"timezone":"UTC"}"#)?;
+    let export = tempdir.path().join("watson.json");
+    std::fs::write(
+        &export,
+        r#"[{"id":"a1","project":"acme","start":"2026-10-17T23:00:00+00:00","stop":"2026-10-17T23:30:00+00:00","tags":[]},
+            {"id":"a2","project":"acme","start":"2026-10-19T01:00:00+00:00","stop":"2026-10-19T02:00:00+00:00","tags":[]}]"#,
+    )?;
+    track(&db, &lockfile, &config)
+        .args(["import", "--from", "watson", "--apply"])
+        .arg(&export)
+        .assert()
+        .success();
+
+    let output = track(&db, &lockfile, &config)
+        .args(["stats", "--from", "2026-10-17", "--to", "2026-10-19"])
+        .output()?;
+    let stats = String::from_utf8(output.stdout)?;
+
+    assert!(stats.contains("sessions        2\n"));
+    assert!(stats.contains("average start   00:00\n"));
+    assert!(stats.contains("longest session 01:00:00  2026-10-19 acme\n"));
+    Ok(())
+}
+
+#[test]
+fn goals_show_progress_against_configured_targets() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    assert!(track(&["goals"])?.starts_with("no goals configured"));
+
+    std::fs::write(
+        &config,
+        r#"{"goals":[{"period":"week","target":"40h"},{"period":"week","target":"10h","project":"acme"}]}"#,
+    )?;
+    track(&["start", "-p", "acme"])?;
+    let goals = track(&["goals"])?;
+
+    let lines: Vec<_> = goals.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("this week "));
+    assert!(lines[0].contains("/ 40:00:00"));
+    assert!(lines[1].starts_with("acme this week "));
+    assert!(lines[1].contains("/ 10:00:00"));
+    Ok(())
+}
+
+#[test]
+fn balance_subtracts_expected_hours_except_days_off() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    std::fs::write(
+        &config,
+        r#"{"timezone":"UTC","schedule":{"since":"2026-10-12","hours":{"Mon":"8h","Tue":"8h","Wed":"8h"},"holidays":"holidays.txt"}}"#,
+    )?;
+    std::fs::write(
+        tempdir.path().join("holidays.txt"),
+        "2026-10-14 Founders Day\n",
+    )?;
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    assert_eq!(
+        track(&["balance", "--until", "2026-10-18"])?,
+        "balance -16:00:00 (2026-10-12 through 2026-10-18)\n"
+    );
+    assert_eq!(
+        track(&["off", "2026-10-13"])?,
+        "2026-10-13 marked as day off\n"
+    );
+    assert_eq!(
+        track(&["balance", "--until", "2026-10-18"])?,
+        "balance -08:00:00 (2026-10-12 through 2026-10-18)\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn report_compares_with_the_preceding_period() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    std::fs::write(&config, r#"{"timezone":"UTC"}"#)?;
+    let export = tempdir.path().join("watson.json");
+    std::fs::write(
+        &export,
+        r#"[{"id":"a1","project":"acme","start":"2026-10-06T09:00:00+00:00","stop":"2026-10-06T11:00:00+00:00","tags":[]},
+            {"id":"a2","project":"acme","start":"2026-10-13T09:00:00+00:00","stop":"2026-10-13T12:00:00+00:00","tags":[]}]"#,
+    )?;
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    track(&[
+        "import",
+        "--from",
+        "watson",
+        "--apply",
+        export.to_str().unwrap(),
+    ])?;
+
+    let report = track(&[
+        "report",
+        "--compare",
+        "previous",
+        "--from",
+        "2026-10-12",
+        "--to",
+        "2026-10-18",
+    ])?;
+
+    let lines: Vec<_> = report.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with("acme"));
+    assert!(lines[1].contains("03:00:00   02:00:00  +01:00:00"));
+    assert!(lines[1].ends_with("+50%"));
+    Ok(())
+}
+
+#[test]
+fn billing_report_prices_billable_time() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    std::fs::write(
+        &config,
+        r#"{"billing":{"currency":"EUR","clients":{"acme":{"rate":90,"projects":["web"]}}}}"#,
+    )?;
+    let export = tempdir.path().join("watson.json");
+    std::fs::write(
+        &export,
+        r#"[{"id":"a1","project":"web","start":"2026-10-13T09:00:00+00:00","stop":"2026-10-13T11:30:00+00:00","tags":[]}]"#,
+    )?;
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    track(&[
+        "import",
+        "--from",
+        "watson",
+        "--apply",
+        export.to_str().unwrap(),
+    ])?;
+
+    let report = track(&[
+        "report",
+        "--billing",
+        "--from",
+        "2026-10-13",
+        "--to",
+        "2026-10-13",
+    ])?;
+
+    let lines: Vec<_> = report.lines().collect();
+    assert!(lines[1].starts_with("acme    web"));
+    assert!(lines[1].ends_with("90.00  225.00 EUR"));
+    assert_eq!(lines[2], "total 225.00 EUR");
+    Ok(())
+}
+
+#[test]
+fn invoice_bills_records_once() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    std::fs::write(
+        &config,
+        r#"{"timezone":"UTC","billing":{"clients":{"acme":{"rate":100,"currency":"EUR","projects":["web"]}}},"invoice":{"tax_percent":10}}"#,
+    )?;
+    let export = tempdir.path().join("watson.json");
+    std::fs::write(
+        &export,
+        r#"[{"id":"a1","project":"web","start":"2026-09-14T09:00:00+00:00","stop":"2026-09-14T11:00:00+00:00","tags":[]}]"#,
+    )?;
+    track(&db, &lockfile, &config)
+        .args(["import", "--from", "watson", "--apply"])
+        .arg(&export)
+        .assert()
+        .success();
+    let invoice = ["invoice", "--client", "acme", "--month", "2026-09"];
+
+    let preview = track(&db, &lockfile, &config)
+        .args(invoice)
+        .arg("--preview")
+        .output()?;
+    assert!(String::from_utf8(preview.stdout)?.starts_with("# Invoice DRAFT\n"));
+
+    let issued = String::from_utf8(
+        track(&db, &lockfile, &config)
+            .args(invoice)
+            .output()?
+            .stdout,
+    )?;
+    assert!(issued.starts_with("# Invoice INV-0001\n"));
+    assert!(issued.contains("| web | 2.00 | 100.00 | 200.00 EUR |"));
+    assert!(issued.contains("| **Total** | | | **220.00 EUR** |"));
+
+    track(&db, &lockfile, &config)
+        .args(invoice)
+        .assert()
+        .failure();
+    Ok(())
+}
+
+#[test]
+fn start_warns_when_a_project_budget_is_nearly_used() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    std::fs::write(
+        &config,
+        r#"{"timezone":"UTC","budgets":{"web":{"limit":"10h"}}}"#,
+    )?;
+    let export = tempdir.path().join("watson.json");
+    std::fs::write(
+        &export,
+        r#"[{"id":"a1","project":"web","start":"2026-10-13T08:00:00+00:00","stop":"2026-10-13T17:00:00+00:00","tags":[]}]"#,
+    )?;
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    track(&[
+        "import",
+        "--from",
+        "watson",
+        "--apply",
+        export.to_str().unwrap(),
+    ])?;
+
+    let started = track(&["start", "-p", "web"])?;
+    assert!(started.contains("Warning: web has used 90% of its budget, past 80%"));
+    track(&["stop"])?;
+
+    let report = track(&["report", "--budget"])?;
+    assert!(report.starts_with("web  09:00:"));
+    assert!(report.contains("  week of 2026-10-12  09:00:00  01:00:00 left\n"));
+    Ok(())
+}
+
+#[test]
+fn closing_a_task_compares_estimate_and_actual_time() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    track(&db, &lockfile, &config)
+        .args(["task", "add", "login", "--estimate", "2h", "-p", "web"])
+        .assert()
+        .success();
+    track(&db, &lockfile, &config)
+        .args(["start", "--task", "missing"])
+        .assert()
+        .failure();
+    track(&db, &lockfile, &config)
+        .args(["start", "--task", "login"])
+        .assert()
+        .success();
+
+    let status = String::from_utf8(
+        track(&db, &lockfile, &config)
+            .arg("status")
+            .output()?
+            .stdout,
+    )?;
+    assert!(status.trim_end().ends_with("web"));
+    track(&db, &lockfile, &config)
+        .args(["task", "close", "login"])
+        .assert()
+        .failure();
+    track(&db, &lockfile, &config)
+        .arg("stop")
+        .assert()
+        .success();
+
+    let closed = String::from_utf8(
+        track(&db, &lockfile, &config)
+            .args(["task", "close", "login"])
+            .output()?
+            .stdout,
+    )?;
+    assert!(closed.starts_with("Closed login  estimate 02:00:00  actual 00:00:00  0"));
+    let open = track(&db, &lockfile, &config)
+        .args(["task", "list"])
+        .output()?;
+    assert!(open.stdout.is_empty());
+    Ok(())
+}
+
+#[test]
+fn pomodoro_records_work_intervals_and_notifies_transitions() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    let phases = tempdir.path().join("phases");
+    std::fs::write(
+        &config,
+        format!(
+            r#"{{"pomodoro":{{"command":"echo $TRACK_POMODORO_PHASE >> {}"}}}}"#,
+            phases.display()
+        ),
+    )?;
+
+    track(&db, &lockfile, &config)
+        .args(["pomodoro", "--work", "1s", "--break", "1s", "--cycles", "2"])
+        .args(["-p", "web"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        std::fs::read_to_string(&phases)?,
+        "work\nbreak\nwork\ndone\n"
+    );
+    let report = String::from_utf8(
+        track(&db, &lockfile, &config)
+            .args(["report", "--last", "1h"])
+            .output()?
+            .stdout,
+    )?;
+    assert!(report.contains("2 pomodoros completed\n"));
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn interrupted_pomodoro_keeps_the_partial_interval() -> TestResult {
+    use std::io::{BufRead, BufReader};
+
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("track"))
+        .arg("--db-dir")
+        .arg(&db)
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .arg("--config")
+        .arg(&config)
+        .args(["pomodoro", "--work", "1m"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()?;
+    let mut lines = BufReader::new(child.stdout.take().expect("stdout is piped")).lines();
+    // the work interval is tracked once its line is printed
+    let started = lines
+        .by_ref()
+        .map_while(|line| line.ok())
+        .any(|line| line.contains("Pomodoro 1/"));
+    assert!(started);
+    std::process::Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()?;
+    let rest: Vec<_> = lines.map_while(|line| line.ok()).collect();
+    assert!(child.wait()?.success());
+    assert_eq!(rest, ["Interrupted after 0 completed pomodoros"]);
+
+    let records = std::fs::read_to_string(&db)?;
+    assert!(records.contains(r#""tags":["pomodoro","interrupted"]"#));
+    assert!(!lockfile.exists() || !std::fs::read_to_string(&lockfile)?.contains("pomodoro"));
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn failing_pre_start_hook_aborts_start() -> TestResult {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    let hooks = tempdir.path().join("hooks");
+    std::fs::create_dir(&hooks)?;
+    let hook = hooks.join("pre-start");
+    std::fs::write(
+        &hook,
+        "#!/bin/sh\necho \"no project given\" >&2\n[ -n \"$TRACK_PROJECT\" ]\n",
+    )?;
+    std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+
+    track(&db, &lockfile, &config)
         .arg("start")
         .assert()
+        .failure();
+    let status = String::from_utf8(
+        track(&db, &lockfile, &config)
+            .arg("status")
+            .output()?
+            .stdout,
+    )?;
+    assert_eq!(status, "No session is running\n");
+
+    track(&db, &lockfile, &config)
+        .args(["start", "-p", "web"])
+        .assert()
+        .success();
+    track(&db, &lockfile, &config)
+        .arg("stop")
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn start_takes_project_and_tags_from_the_git_branch() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    std::fs::write(&config, r#"{"git":{"enabled":true}}"#)?;
+    let repo = tempdir.path().join("shop");
+    std::fs::create_dir(&repo)?;
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .current_dir(&repo)
+            .args(["-c", "user.name=track", "-c", "user.email=track@localhost"])
+            .args(args)
+            .output()
+    };
+    git(&["init", "--quiet", "--initial-branch", "main"])?;
+    git(&["commit", "--quiet", "--allow-empty", "-m", "initial"])?;
+    git(&["checkout", "--quiet", "-b", "feature/ABC-7-cart"])?;
+    let track = || {
+        let mut cmd = track(&db, &lockfile, &config);
+        cmd.current_dir(&repo);
+        cmd
+    };
+    let post_checkout = || -> TestResult<String> {
+        let output = track()
+            .args(["git-hook", "post-checkout", "0000", "1111", "1"])
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    track()
+        .args(["start", "-t", "review", "-m", "cart totals"])
+        .assert()
         .success();
+    let running = std::fs::read_to_string(&lockfile)?;
+    assert!(running.contains(r#""project":"shop","tags":["review","feature/ABC-7-cart","ABC-7"]"#));
+
+    git(&["checkout", "--quiet", "-b", "feature/ABC-8-pay"])?;
+    assert!(post_checkout()?.contains("Switched tracking to feature/ABC-8-pay"));
+    let running = std::fs::read_to_string(&lockfile)?;
+    assert!(running.contains(r#""tags":["review","feature/ABC-8-pay","ABC-8"]"#));
+    assert!(running.contains("cart totals"));
+    assert!(std::fs::read_to_string(&db)?.contains("ABC-7"));
+
+    // main is not tagged, but leaving the feature branch still ends its session
+    git(&["checkout", "--quiet", "main"])?;
+    assert!(post_checkout()?.contains("Switched tracking to main"));
+    let running = std::fs::read_to_string(&lockfile)?;
+    assert!(running.contains(r#""tags":["review"]"#));
+
+    track().args(["git-hook", "install"]).assert().success();
+    let hook = std::fs::read_to_string(repo.join(".git/hooks/post-checkout"))?;
+    assert!(hook.contains("git-hook post-checkout \"$@\""));
+    Ok(())
+}
+
+#[test]
+fn prompt_prints_the_running_timer_only() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    assert_eq!(track(&["prompt"])?, "");
+    assert!(track(&["prompt", "--waybar"])?.contains(r#""class":"idle""#));
+
+    track(&["start", "-p", "web"])?;
+    assert_eq!(track(&["prompt"])?, "web 0m\n");
+    assert_eq!(
+        track(&["prompt", "--format", "[{project}|{state}]"])?,
+        "[web|running]\n"
+    );
+    let block = track(&["prompt", "--waybar"])?;
+    assert!(block.starts_with(r#"{"text":"web 0m","#));
+    assert!(block.contains(r#""class":"running""#));
+    Ok(())
+}
+
+#[test]
+fn metrics_are_written_for_the_textfile_collector() -> TestResult {
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    assert!(track(&["metrics"])?.contains("track_running{timer=\"default\"} 0\n"));
+
+    track(&["start", "-p", "web"])?;
+    let file = tempdir.path().join("track.prom");
+    assert_eq!(track(&["metrics", "-o", file.to_str().unwrap()])?, "");
+    let text = std::fs::read_to_string(&file)?;
+    assert!(text.contains("# TYPE track_session_seconds gauge\n"));
+    assert!(text.contains("track_running{timer=\"default\"} 1\n"));
+    assert!(text.contains("track_session_seconds{timer=\"default\",project=\"web\"} 0\n"));
+    assert!(text.contains("track_week_seconds{project=\"web\"} 0\n"));
+    Ok(())
+}
+
+#[test]
+fn serve_answers_json_requests() -> TestResult {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("track"))
+        .arg("--db-dir")
+        .arg(&db)
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .arg("--config")
+        .arg(&config)
+        .args(["serve", "--bind", "127.0.0.1:0"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()?;
+    let mut banner = String::new();
+    BufReader::new(child.stdout.take().unwrap()).read_line(&mut banner)?;
+    let addr = banner.trim().trim_start_matches("Listening on http://");
+    let request = |head: &str| -> Result<String, testresult::TestError> {
+        let mut stream = std::net::TcpStream::connect(addr)?;
+        write!(
+            stream,
+            "{head}\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    };
+
+    let form = request("POST /start HTTP/1.1\r\nContent-Type: text/plain\r\nContent-Length: 0");
+    let started =
+        request("POST /start HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: 0");
+    let status = request("GET /status HTTP/1.1");
+    child.kill()?;
+    child.wait()?;
+
+    assert!(form?.starts_with("HTTP/1.1 415"));
+    assert!(started?.starts_with("HTTP/1.1 201"));
+    let status = status?;
+    assert!(status.starts_with("HTTP/1.1 200"));
+    assert!(status.contains(r#""running":true"#));
+    assert!(lockfile.exists());
     Ok(())
 }
+
+#[cfg(unix)]
+#[test]
+fn track_uses_a_running_daemon() -> TestResult {
+    use std::io::{BufRead, BufReader};
+
+    let (tempdir, lockfile, db) = tracking_paths();
+    let config = tempdir.path().join("config.json");
+    let socket = tempdir.path().join("trackd.sock");
+    let mut daemon = std::process::Command::new(assert_cmd::cargo::cargo_bin("trackd"))
+        .arg("--db-dir")
+        .arg(&db)
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .arg("--socket")
+        .arg(&socket)
+        .stdout(std::process::Stdio::piped())
+        .spawn()?;
+    let mut banner = String::new();
+    BufReader::new(daemon.stdout.take().unwrap()).read_line(&mut banner)?;
+    let track = |args: &[&str]| -> Result<String, testresult::TestError> {
+        let output = Command::cargo_bin("track")?
+            .arg("--db-dir")
+            .arg(&db)
+            .arg("--lockfile")
+            .arg(&lockfile)
+            .arg("--config")
+            .arg(&config)
+            .arg("--socket")
+            .arg(&socket)
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let result = (|| -> TestResult {
+        // a client that never sends anything does not hold up the others
+        let _silent = std::os::unix::net::UnixStream::connect(&socket)?;
+        let started = std::time::Instant::now();
+        track(&["start", "-p", "web"])?;
+        track(&["stop"])?;
+        assert!(started.elapsed() < std::time::Duration::from_secs(4));
+        assert!(std::fs::read_to_string(&db)?.contains(r#""project":"web""#));
+        assert!(track(&["log", "--last", "1h"])?.contains("web"));
+        // the daemon notices the database changed under it
+        std::fs::write(&db, r#"{"records":[]}"#)?;
+        assert!(!track(&["log", "--last", "1h"])?.contains("web"));
+        Ok(())
+    })();
+    daemon.kill()?;
+    daemon.wait()?;
+    result
+}